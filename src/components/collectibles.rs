@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+/// Identifies a kind of collectible. Kinds are data registered in
+/// [`CollectibleKinds`](crate::resources::CollectibleKinds), so adding one needs no new enum
+/// variant or match arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CollectibleKind(pub &'static str);
+
+impl CollectibleKind {
+    pub const COIN: Self = Self("coin");
+    pub const GEM: Self = Self("gem");
+    pub const HEALTH: Self = Self("health");
+    pub const KEY: Self = Self("key");
+    pub const POWER_UP: Self = Self("power_up");
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Collectible {
+    pub kind: CollectibleKind,
+    pub value: u32,
+}
+
+impl Collectible {
+    pub const fn new(kind: CollectibleKind, value: u32) -> Self {
+        Self { kind, value }
+    }
+}
+
+/// Marks a collectible that has already been picked up, so it can never count twice while its
+/// despawn is pending.
+#[derive(Component)]
+pub struct PickedUp;
+
+/// Idle bob, spin and shimmer. Lives on the collectible's sprite, a child of the entity with the
+/// collider, so the animation never touches the hitbox.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct IdleAnimation {
+    pub base_color: Color,
+    /// Offsets the cycle so neighbouring collectibles don't move in lockstep.
+    pub phase: f32,
+}
+
+/// A collected coin on its way to the HUD slot it fills.
+#[derive(Component, Debug)]
+pub struct CoinFlight {
+    pub start: Vec2,
+    pub slot: usize,
+    pub value: u32,
+    pub timer: Timer,
+}
+
+/// HUD text showing how many of a kind have been collected.
+#[derive(Component)]
+pub struct CollectibleCounter(pub CollectibleKind);
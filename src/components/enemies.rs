@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Enemy {
+    /// Horizontal heading, `1.0` for right and `-1.0` for left.
+    pub direction: f32,
+    pub speed: f32,
+}
+
+#[derive(Component, Clone, Debug)]
+pub enum EnemyBehavior {
+    /// Walk until a wall or the edge of the current platform, then turn around.
+    Patrol,
+    /// Walk towards each point in turn, looping back to the first.
+    Waypoints { points: Vec<Vec2>, next: usize },
+    /// Patrol, but run at a player within `range`, stopping at edges instead of falling off.
+    Chase { range: f32, speed: f32 },
+}
+
+/// Platform an entity is standing on.
+#[derive(Clone, Copy, Debug)]
+pub struct GroundContact {
+    pub entity: Entity,
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+}
+
+/// Ground and wall contacts shared by the player and enemies, refreshed each frame by
+/// `detect_surroundings` so both make the same call about edges.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Surroundings {
+    pub half_size: Vec2,
+    pub ground: Option<GroundContact>,
+    pub wall_left: bool,
+    pub wall_right: bool,
+}
+
+impl Surroundings {
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            half_size: size * 0.5,
+            ground: None,
+            wall_left: false,
+            wall_right: false,
+        }
+    }
+
+    pub const fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+
+    /// Whether there is a wall on the side `direction` points to.
+    pub fn wall_ahead(&self, direction: f32) -> bool {
+        if direction > 0.0 {
+            self.wall_right
+        } else {
+            self.wall_left
+        }
+    }
+
+    /// Whether the leading side of a body at `x` has reached the end of its platform.
+    pub fn edge_ahead(&self, x: f32, direction: f32) -> bool {
+        let Some(ground) = self.ground else {
+            return false;
+        };
+        if direction > 0.0 {
+            x + self.half_size.x >= ground.right
+        } else {
+            x - self.half_size.x <= ground.left
+        }
+    }
+}
@@ -1,31 +1,182 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::InputAction;
 
+use super::PowerUpKind;
+use crate::resources::KeyboardHalf;
+
 #[derive(Component)]
 pub struct Player;
 
+/// Which local player this is, counting from zero.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlayerNumber(pub usize);
+
+impl PlayerNumber {
+    /// `P1`, `P2`, ...
+    pub fn label(self) -> String {
+        format!("P{}", self.0 + 1)
+    }
+}
+
+/// The devices driving a player's [`PlayerInput`] context. Keyboard bindings outside the
+/// player's half are left out, as is every gamepad but their own.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InputDevices {
+    pub keyboard: Option<KeyboardHalf>,
+    pub gamepad: Option<Entity>,
+}
+
+/// A player whose gamepad disconnected. The next pad to connect goes to them.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AwaitingGamepad;
+
 #[derive(Component)]
 pub struct Ground;
 
+/// Reaching this completes the level.
+#[derive(Component)]
+pub struct Goal;
+
+#[derive(Component)]
+pub struct ScoreText;
+
 #[derive(Component)]
 pub struct PlayerInput;
 
+/// Input context for the mouse pointer, separate from any one player.
+#[derive(Component)]
+pub struct PointerInput;
+
+/// Input context for menus. Only active while a menu is open, and takes priority over
+/// [`PlayerInput`].
+#[derive(Component)]
+pub struct MenuInput;
+
 #[derive(Component)]
 pub struct HudText;
 
 #[derive(Component)]
-pub struct Coin;
+pub struct LivesText;
 
 #[derive(Component)]
 pub struct CoinSlot {
     pub index: usize,
 }
 
+/// Container for the coin slots, rebuilt whenever the level's coin total changes.
+#[derive(Component, Default)]
+pub struct CoinCounter {
+    /// Total the current slots were built for.
+    pub total: usize,
+}
+
+/// Numeric "x / y" coin display used when there are too many slots to fit.
+#[derive(Component)]
+pub struct CoinCounterText;
+
 #[derive(Component, Clone, Copy)]
 pub struct Platform {
     pub size: Vec2,
 }
 
+/// Horizontal heading, `1.0` for right and `-1.0` for left.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Facing(pub f32);
+
+impl Default for Facing {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovementStats {
+    pub speed: f32,
+    pub jump_speed: f32,
+    pub gravity_scale: f32,
+}
+
+/// What put a [`MovementModifier`] on the stack, so each source only removes its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModifierSource {
+    PowerUp(PowerUpKind),
+    /// Anything that isn't a power-up, such as a surface or a status effect.
+    Other(&'static str),
+}
+
+impl ModifierSource {
+    pub const fn is_power_up(self) -> bool {
+        matches!(self, Self::PowerUp(_))
+    }
+}
+
+/// Multiplies movement stats while its source is active.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovementModifier {
+    pub source: ModifierSource,
+    pub speed: f32,
+    pub jump_speed: f32,
+    pub gravity_scale: f32,
+}
+
+/// Base movement stats plus the modifiers stacked on top of them.
+#[derive(Component, Clone, Debug)]
+pub struct Movement {
+    pub base: MovementStats,
+    pub modifiers: Vec<MovementModifier>,
+}
+
+impl Movement {
+    pub const fn new(base: MovementStats) -> Self {
+        Self {
+            base,
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn current(&self) -> MovementStats {
+        self.modifiers
+            .iter()
+            .fold(self.base, |stats, modifier| MovementStats {
+                speed: stats.speed * modifier.speed,
+                jump_speed: stats.jump_speed * modifier.jump_speed,
+                gravity_scale: stats.gravity_scale * modifier.gravity_scale,
+            })
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub const fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub const fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+}
+
+/// Ignores non-lethal damage until the timer finishes.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+/// Suppresses movement input while knockback plays out.
+#[derive(Component)]
+pub struct Staggered {
+    pub timer: Timer,
+}
+
 #[derive(InputAction)]
 #[action_output(Vec2)]
 pub struct Move;
@@ -33,3 +184,41 @@ pub struct Move;
 #[derive(InputAction)]
 #[action_output(bool)]
 pub struct Jump;
+
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Shoot;
+
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Interact;
+
+/// Primary mouse button, for clicking and dragging in the world.
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Click;
+
+/// Opens or closes the pause menu. Bound in both gameplay and menu contexts.
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Pause;
+
+/// Restarts the run. Bound in both gameplay and menu contexts.
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Restart;
+
+/// Moves the focus between menu buttons.
+#[derive(InputAction)]
+#[action_output(Vec2)]
+pub struct Navigate;
+
+/// Presses the focused menu button.
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Confirm;
+
+/// Closes the top menu.
+#[derive(InputAction)]
+#[action_output(bool)]
+pub struct Back;
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::events::Damage;
+
+/// Sensor volume that routes contact into [`Damage`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Hazard {
+    pub damage: u32,
+    pub lethal: bool,
+    /// Only victims on this side of the hazard get hurt, e.g. the pointy side of spikes.
+    pub facing: Option<Dir2>,
+    pub knockback: f32,
+    /// Disarmed hazards are ignored, e.g. a crusher's underside while it rises.
+    pub armed: bool,
+}
+
+impl Hazard {
+    pub const fn lava() -> Self {
+        Self {
+            damage: 0,
+            lethal: true,
+            facing: None,
+            knockback: 0.0,
+            armed: true,
+        }
+    }
+
+    pub const fn spikes(facing: Dir2, damage: u32, knockback: f32) -> Self {
+        Self {
+            damage,
+            lethal: false,
+            facing: Some(facing),
+            knockback,
+            armed: true,
+        }
+    }
+
+    pub const fn crusher() -> Self {
+        Self {
+            damage: 0,
+            lethal: true,
+            facing: Some(Dir2::NEG_Y),
+            knockback: 0.0,
+            armed: false,
+        }
+    }
+
+    /// Whether a victim at `offset` from the hazard's center is on its dangerous side.
+    pub fn hits(&self, offset: Vec2) -> bool {
+        self.armed && self.facing.is_none_or(|facing| offset.dot(*facing) > 0.0)
+    }
+
+    pub fn damage_to(&self, victim: Entity) -> Damage {
+        let damage = if self.lethal {
+            Damage::lethal(victim)
+        } else {
+            Damage::new(victim, self.damage)
+        };
+        let knockback = self
+            .facing
+            .map_or(Vec2::ZERO, |facing| *facing * self.knockback);
+        damage.with_knockback(knockback)
+    }
+}
+
+/// Timings, in seconds, for each phase of a crusher's cycle.
+#[derive(Clone, Copy, Debug)]
+pub struct CrusherSchedule {
+    pub wait: f32,
+    pub slam: f32,
+    pub hold: f32,
+    pub rise: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrusherPhase {
+    Waiting,
+    Slamming,
+    Holding,
+    Rising,
+}
+
+impl CrusherPhase {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Waiting => Self::Slamming,
+            Self::Slamming => Self::Holding,
+            Self::Holding => Self::Rising,
+            Self::Rising => Self::Waiting,
+        }
+    }
+}
+
+/// Kinematic block that slams along `travel` and back on a fixed schedule.
+#[derive(Component, Debug)]
+pub struct Crusher {
+    pub origin: Vec2,
+    pub travel: Vec2,
+    pub schedule: CrusherSchedule,
+    pub phase: CrusherPhase,
+    pub timer: Timer,
+    /// Lethal sensor on the leading face, armed only while slamming.
+    pub hitbox: Entity,
+}
+
+impl Crusher {
+    pub fn new(origin: Vec2, travel: Vec2, schedule: CrusherSchedule, hitbox: Entity) -> Self {
+        Self {
+            origin,
+            travel,
+            schedule,
+            phase: CrusherPhase::Waiting,
+            timer: Timer::from_seconds(schedule.wait, TimerMode::Once),
+            hitbox,
+        }
+    }
+
+    pub fn phase_duration(&self, phase: CrusherPhase) -> f32 {
+        match phase {
+            CrusherPhase::Waiting => self.schedule.wait,
+            CrusherPhase::Slamming => self.schedule.slam,
+            CrusherPhase::Holding => self.schedule.hold,
+            CrusherPhase::Rising => self.schedule.rise,
+        }
+    }
+
+    /// Velocity that covers the phase's distance in its duration.
+    pub fn phase_velocity(&self) -> Vec2 {
+        match self.phase {
+            CrusherPhase::Slamming => self.travel / self.schedule.slam.max(f32::EPSILON),
+            CrusherPhase::Rising => -self.travel / self.schedule.rise.max(f32::EPSILON),
+            CrusherPhase::Waiting | CrusherPhase::Holding => Vec2::ZERO,
+        }
+    }
+
+    /// Where the crusher rests once the current phase ends.
+    pub fn phase_end(&self) -> Vec2 {
+        match self.phase {
+            CrusherPhase::Slamming | CrusherPhase::Holding => self.origin + self.travel,
+            CrusherPhase::Rising | CrusherPhase::Waiting => self.origin,
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.phase = self.phase.next();
+        let duration = self.phase_duration(self.phase);
+        self.timer.set_duration(Duration::from_secs_f32(duration));
+        self.timer.reset();
+    }
+}
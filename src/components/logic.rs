@@ -0,0 +1,70 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// Names a piece of level logic so switches can target it from level data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LogicId(pub &'static str);
+
+/// Receives [`LogicSignal`](crate::events::LogicSignal)s sent to this ID.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LogicTarget(pub LogicId);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwitchKind {
+    /// Active while something stands on it.
+    PressurePlate,
+    /// Toggles each time the player interacts with it.
+    Lever,
+    /// Activates on touch and switches itself off after `seconds`.
+    Timed { seconds: f32 },
+}
+
+#[derive(Component, Debug)]
+pub struct Switch {
+    pub kind: SwitchKind,
+    pub targets: Vec<LogicId>,
+    pub active: bool,
+    pub timer: Timer,
+}
+
+impl Switch {
+    pub fn new(kind: SwitchKind, targets: Vec<LogicId>) -> Self {
+        let seconds = match kind {
+            SwitchKind::Timed { seconds } => seconds,
+            SwitchKind::PressurePlate | SwitchKind::Lever => 0.0,
+        };
+        Self {
+            kind,
+            targets,
+            active: false,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// A door that slides up out of the way when opened. A locked door ignores signals and only
+/// opens for a player carrying the matching key.
+#[derive(Component, Debug)]
+pub struct Door {
+    pub lock: Option<LogicId>,
+    pub closed_position: Vec2,
+    pub open: bool,
+    /// How far open the door is, from `0.0` closed to `1.0` open.
+    pub openness: f32,
+}
+
+/// Moves between `origin` and `origin + travel` depending on whether it is powered.
+#[derive(Component, Debug)]
+pub struct Mechanism {
+    pub origin: Vec2,
+    pub travel: Vec2,
+    pub speed: f32,
+    pub powered: bool,
+}
+
+/// The door a key collectible opens.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct KeyId(pub LogicId);
+
+/// Keys the player has picked up.
+#[derive(Component, Default, Debug)]
+pub struct KeyRing(pub HashSet<LogicId>);
@@ -1,3 +1,15 @@
+pub(super) mod collectibles;
+pub(super) mod enemies;
 pub(super) mod game;
+pub(super) mod hazards;
+pub(super) mod logic;
+pub(super) mod power_ups;
+pub(super) mod projectiles;
 
+pub use collectibles::*;
+pub use enemies::*;
 pub use game::*;
+pub use hazards::*;
+pub use logic::*;
+pub use power_ups::*;
+pub use projectiles::*;
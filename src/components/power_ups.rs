@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    Speed,
+    HighJump,
+    LowGravity,
+    Magnet,
+    Shield,
+}
+
+impl PowerUpKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Speed => "Speed",
+            Self::HighJump => "High Jump",
+            Self::LowGravity => "Low Gravity",
+            Self::Magnet => "Magnet",
+            Self::Shield => "Shield",
+        }
+    }
+}
+
+/// What picking up a power-up does when the same kind is already active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StackPolicy {
+    /// Restart the timer at the full duration.
+    Refresh,
+    /// Add the full duration to whatever time is left.
+    Extend,
+    /// Add a stack, up to `max`, and restart the timer.
+    Stack { max: u32 },
+}
+
+/// Marks a power-up collectible with the effect it grants.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PowerUp(pub PowerUpKind);
+
+#[derive(Clone, Debug)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub stacks: u32,
+    pub timer: Timer,
+}
+
+/// Power-ups currently affecting an entity.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ActivePowerUps(pub Vec<ActivePowerUp>);
+
+impl ActivePowerUps {
+    pub fn get(&self, kind: PowerUpKind) -> Option<&ActivePowerUp> {
+        self.0.iter().find(|active| active.kind == kind)
+    }
+
+    pub fn has(&self, kind: PowerUpKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn grant(&mut self, kind: PowerUpKind, duration: f32, policy: StackPolicy) {
+        let Some(active) = self.0.iter_mut().find(|active| active.kind == kind) else {
+            self.0.push(ActivePowerUp {
+                kind,
+                stacks: 1,
+                timer: Timer::from_seconds(duration, TimerMode::Once),
+            });
+            return;
+        };
+
+        match policy {
+            StackPolicy::Refresh => {
+                active.timer.set_duration(Duration::from_secs_f32(duration));
+            }
+            StackPolicy::Extend => {
+                let remaining = active.timer.remaining();
+                active
+                    .timer
+                    .set_duration(remaining + Duration::from_secs_f32(duration));
+            }
+            StackPolicy::Stack { max } => {
+                active.stacks = (active.stacks + 1).min(max);
+                active.timer.set_duration(Duration::from_secs_f32(duration));
+            }
+        }
+        active.timer.reset();
+    }
+
+    pub fn remove(&mut self, kind: PowerUpKind) {
+        self.0.retain(|active| active.kind != kind);
+    }
+}
+
+/// Non-lethal damage is absorbed while this is present.
+#[derive(Component)]
+pub struct Shielded;
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Team {
+    Player,
+    Enemy,
+}
+
+/// Everything needed to launch a projectile.
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectileSpec {
+    pub speed: f32,
+    /// Seconds before the projectile is recycled without hitting anything.
+    pub lifetime: f32,
+    pub gravity_scale: f32,
+    pub damage: u32,
+    pub knockback: f32,
+    pub radius: f32,
+    pub color: Color,
+}
+
+/// An in-flight projectile. Parked pool entries don't have this component.
+#[derive(Component, Debug)]
+pub struct Projectile {
+    pub team: Team,
+    pub damage: u32,
+    pub knockback: f32,
+    pub lifetime: Timer,
+}
+
+/// Marks entities owned by the projectile pool, whether in flight or parked.
+#[derive(Component)]
+pub struct PooledProjectile;
+
+#[derive(Component, Debug)]
+pub struct Shooter {
+    pub team: Team,
+    pub spec: ProjectileSpec,
+    pub cooldown: Timer,
+    /// How close a target needs to be before AI shooters open fire.
+    pub range: f32,
+}
+
+impl Shooter {
+    pub fn new(team: Team, spec: ProjectileSpec, cooldown: f32, range: f32) -> Self {
+        let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+        // Ready to fire straight away.
+        cooldown.finish();
+        Self {
+            team,
+            spec,
+            cooldown,
+            range,
+        }
+    }
+
+    pub fn ready(&self) -> bool {
+        self.cooldown.is_finished()
+    }
+}
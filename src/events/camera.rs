@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+/// Points the camera at `entity` for `secs`, then hands it back to the usual target.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct FocusCamera {
+    pub entity: Entity,
+    pub secs: f32,
+}
+
+/// Adds trauma to the camera shake. Trauma is clamped to `1.0` and decays over time.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+/// Knocks the camera by `offset` in world units; it springs back on its own.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CameraKick {
+    pub offset: Vec2,
+}
+
+/// Freezes virtual time for `secs` of real time to sell an impact.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HitStop {
+    pub secs: f32,
+}
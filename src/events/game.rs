@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::components::{CollectibleKind, LogicId, ProjectileSpec, Team};
+
+/// Shared damage pipeline: hazards, enemies and projectiles trigger this instead of touching
+/// [`Health`](crate::components::Health) directly.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct Damage {
+    pub entity: Entity,
+    pub amount: u32,
+    /// Lethal damage ignores invulnerability and empties the target's health.
+    pub lethal: bool,
+    /// Velocity applied to the target when the hit lands.
+    pub knockback: Vec2,
+}
+
+impl Damage {
+    pub const fn new(entity: Entity, amount: u32) -> Self {
+        Self {
+            entity,
+            amount,
+            lethal: false,
+            knockback: Vec2::ZERO,
+        }
+    }
+
+    pub const fn lethal(entity: Entity) -> Self {
+        Self {
+            entity,
+            amount: 0,
+            lethal: true,
+            knockback: Vec2::ZERO,
+        }
+    }
+
+    pub const fn with_knockback(mut self, knockback: Vec2) -> Self {
+        self.knockback = knockback;
+        self
+    }
+}
+
+/// Triggered once when an entity's health reaches zero.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct Died {
+    pub entity: Entity,
+}
+
+/// Launches a projectile from the pool.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct FireProjectile {
+    pub origin: Vec2,
+    pub direction: Dir2,
+    pub team: Team,
+    pub spec: ProjectileSpec,
+}
+
+/// Triggered on a projectile right before it returns to the pool after striking something.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct ProjectileHit {
+    pub entity: Entity,
+    pub target: Entity,
+    pub position: Vec2,
+}
+
+/// Triggered when a player picks up a collectible. Each kind's effect is an observer of this,
+/// so new kinds plug in without touching the pickup system.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Collected {
+    pub collector: Entity,
+    pub item: Entity,
+    pub kind: CollectibleKind,
+    pub value: u32,
+    pub position: Vec2,
+}
+
+/// Triggered on a coin when it is picked up. Audio, particles, the HUD and achievements each
+/// observe this independently.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct CoinCollected {
+    pub entity: Entity,
+    pub collector: Entity,
+    pub position: Vec2,
+    pub value: u32,
+}
+
+/// Sent by switches to every [`LogicTarget`](crate::components::LogicTarget) with a matching ID.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LogicSignal {
+    /// The switch sending the signal.
+    pub source: Entity,
+    pub target: LogicId,
+    pub active: bool,
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::resources::{Control, InputBinding};
+
+/// Asks for `control` to be bound to `binding`, replacing its other bindings on the same
+/// device.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RebindControl {
+    pub control: Control,
+    pub binding: InputBinding,
+}
+
+/// Puts every control back to the bindings from the config asset.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ResetBindings;
+
+/// An input bound to two controls at once.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BindingConflict {
+    pub binding: InputBinding,
+    /// The control already using the input.
+    pub existing: Control,
+    /// The control it was also bound, or asked to be bound, to.
+    pub requested: Control,
+}
+
+impl fmt::Display for BindingConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is already bound to {}",
+            self.binding,
+            self.existing.label()
+        )
+    }
+}
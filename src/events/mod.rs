@@ -0,0 +1,7 @@
+pub(super) mod camera;
+pub(super) mod game;
+pub(super) mod input;
+
+pub use camera::*;
+pub use game::*;
+pub use input::*;
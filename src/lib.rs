@@ -3,16 +3,19 @@
 use bevy::prelude::*;
 
 mod components;
+mod events;
 mod plugins;
 mod resources;
+mod states;
+mod storage;
 mod third_party;
 mod utils;
 
-/// Use this module instead of importing the `components`, `plugins`, `resources`, and `utils`
-/// modules directly.
+/// Use this module instead of importing the `components`, `events`, `plugins`, `resources`,
+/// `states`, and `utils` modules directly.
 mod prelude {
     pub use super::*;
-    pub use {components::*, plugins::*, resources::*, utils::*};
+    pub use {components::*, events::*, plugins::*, resources::*, states::*, utils::*};
 }
 
 pub struct AppPlugin;
@@ -26,6 +29,7 @@ impl Plugin for AppPlugin {
             plugins::fonts::plugin,
             plugins::game::plugin,
             plugins::input::plugin,
+            plugins::menus::plugin,
             plugins::physics::plugin,
         ));
 
@@ -34,7 +38,40 @@ impl Plugin for AppPlugin {
         app.add_plugins(plugins::debug::plugin);
     }
 }
-pub use components::{Coin, CoinSlot, Player};
+pub use components::{
+    ActivePowerUps, AwaitingGamepad, CoinSlot, Collectible, CollectibleKind, Door, Enemy,
+    EnemyBehavior, Goal, Ground, Hazard, Health, InputDevices, KeyRing, LogicId, LogicTarget,
+    Mechanism, ModifierSource, Movement, MovementModifier, MovementStats, PickedUp, Platform,
+    Player, PlayerNumber, PowerUp, PowerUpKind, StackPolicy, Surroundings, Switch, SwitchKind,
+};
+pub use events::{
+    BindingConflict, CameraKick, CameraShake, CoinCollected, Collected, Damage, Died, FocusCamera,
+    HitStop, LogicSignal, RebindControl, ResetBindings,
+};
+pub use plugins::camera::{
+    CameraBounds, CameraConfiner, CameraEffects, CameraFollow, CameraFrame, CameraTarget,
+    CameraZoom, MainCamera, PixelPerfect, UiResolution,
+};
+pub use plugins::game::{
+    Achievement, AchievementUnlocked, Achievements, REPLAY_KEY, SPLIT_KEYBOARD_KEY,
+};
 pub use plugins::game::{COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, HUD_TEXT_COLOR};
-pub use plugins::game::{collect_coins, update_coin_counter};
-pub use resources::CoinState;
+pub use plugins::game::{
+    CollectibleSpec, DoorSpec, EnemySpec, HazardKind, HazardSpec, LevelData, MechanismSpec,
+    ParallaxLayer, ParallaxSpec, PlatformSpec, SwitchSpec,
+};
+pub use plugins::game::{
+    RegisterCollectibleExt, add_coins, announce_coin_collected, collect_collectibles,
+    count_spawned_coins, detect_surroundings, spawn_collectibles, update_coin_counter,
+};
+pub use plugins::input::{
+    Glyph, InputPrompt, LastInputDevice, MousePosition, TouchControls, control_prompts,
+    input_prompt, prompt_glyphs,
+};
+pub use resources::{
+    Accessibility, CoinState, CollectedCounts, CollectibleKindInfo, CollectibleKinds, Control,
+    ControlBindings, HighScoreEntry, HighScores, InputBinding, InputReplay, JoinedPlayers,
+    KeyboardHalf, LevelResult, Lives, LogicInputs, PlayerActions, Replay, RunSeed, RunStats, Score,
+    ScoreBonus,
+};
+pub use states::{GameState, InputMode, PauseState};
@@ -1,13 +0,0 @@
-use bevy::prelude::*;
-
-#[derive(Component)]
-#[require(Camera2d)]
-pub struct MainCamera;
-
-pub(crate) fn plugin(app: &mut App) {
-    app.add_systems(Startup, initialize_camera);
-}
-
-fn initialize_camera(mut commands: Commands) {
-    commands.spawn(MainCamera);
-}
@@ -0,0 +1,88 @@
+//! Keeps the camera inside the level. Bounds are either one rectangle or a set of rooms; with
+//! rooms the camera stays in the room holding its target and pans over when the target
+//! crosses into another one.
+
+use bevy::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CameraBounds {
+    /// The whole level is one region.
+    Level(Rect),
+    /// Separate regions, each confining the camera while the target is inside it.
+    Rooms(Vec<Rect>),
+}
+
+/// A pan from one room to the next.
+#[derive(Clone, Debug)]
+pub struct RoomTransition {
+    pub from: Vec2,
+    pub timer: Timer,
+}
+
+/// Confines a [`CameraFollow`](super::CameraFollow) camera.
+#[derive(Component, Clone, Debug)]
+pub struct CameraConfiner {
+    pub rooms: Vec<Rect>,
+    pub active: usize,
+    /// How long a room-to-room pan takes.
+    pub transition_secs: f32,
+    pub transition: Option<RoomTransition>,
+}
+
+impl CameraConfiner {
+    pub fn new(bounds: CameraBounds) -> Self {
+        let rooms = match bounds {
+            CameraBounds::Level(rect) => vec![rect],
+            CameraBounds::Rooms(rooms) => rooms,
+        };
+        Self {
+            rooms,
+            active: 0,
+            transition_secs: 0.6,
+            transition: None,
+        }
+    }
+
+    pub fn room_containing(&self, point: Vec2) -> Option<usize> {
+        self.rooms.iter().position(|room| room.contains(point))
+    }
+
+    /// Clamps a camera center so a view of `view_size` stays inside the active room. Rooms
+    /// smaller than the view are centered instead.
+    pub fn clamp(&self, center: Vec2, view_size: Vec2) -> Vec2 {
+        let Some(room) = self.rooms.get(self.active) else {
+            return center;
+        };
+        clamp_to_room(center, view_size, *room)
+    }
+}
+
+pub fn clamp_to_room(center: Vec2, view_size: Vec2, room: Rect) -> Vec2 {
+    let half_view = view_size * 0.5;
+    let clamp_axis = |center: f32, half_view: f32, min: f32, max: f32| {
+        if max - min <= half_view * 2.0 {
+            (min + max) * 0.5
+        } else {
+            center.clamp(min + half_view, max - half_view)
+        }
+    };
+    Vec2::new(
+        clamp_axis(center.x, half_view.x, room.min.x, room.max.x),
+        clamp_axis(center.y, half_view.y, room.min.y, room.max.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_inside_large_rooms_and_centers_small_ones() {
+        let view = Vec2::new(200.0, 100.0);
+        let wide = Rect::new(0.0, 0.0, 1000.0, 80.0);
+
+        let clamped = clamp_to_room(Vec2::new(-50.0, 500.0), view, wide);
+        // Wide enough to clamp on X, too short on Y so it centers.
+        assert_eq!(clamped, Vec2::new(100.0, 40.0));
+    }
+}
@@ -0,0 +1,213 @@
+//! Camera feel: trauma-based shake, kicks and hit-stop. Gameplay triggers [`CameraShake`],
+//! [`CameraKick`] and [`HitStop`]; everything here is skipped under reduced motion.
+//!
+//! The shake offset is taken off the camera before following and put back afterwards, so the
+//! follow never sees it.
+
+use bevy::prelude::*;
+
+use crate::{
+    events::{CameraKick, CameraShake, HitStop},
+    resources::Accessibility,
+};
+
+use super::{CameraMovement, MainCamera, follow::smooth_damp};
+
+/// Shake and kick state for a camera.
+#[derive(Component, Clone, Debug)]
+pub struct CameraEffects {
+    /// Current trauma in `0.0..=1.0`. Shake strength is its square.
+    pub trauma: f32,
+    /// Trauma lost per second.
+    pub decay: f32,
+    pub max_offset: Vec2,
+    /// Largest roll, in radians.
+    pub max_angle: f32,
+    /// How fast the shake noise is sampled.
+    pub frequency: f32,
+    pub kick: Vec2,
+    pub kick_velocity: Vec2,
+    /// How long a kick takes to spring back.
+    pub kick_smooth_time: f32,
+    /// The offset applied last frame, removed again before the follow runs.
+    pub applied: Vec2,
+}
+
+impl Default for CameraEffects {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: Vec2::new(24.0, 18.0),
+            max_angle: 0.05,
+            frequency: 18.0,
+            kick: Vec2::ZERO,
+            kick_velocity: Vec2::ZERO,
+            kick_smooth_time: 0.12,
+            applied: Vec2::ZERO,
+        }
+    }
+}
+
+/// Counts down a hit-stop in real time while virtual time is frozen.
+#[derive(Resource, Default)]
+struct HitStopTimer(Option<Timer>);
+
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(super) enum CameraEffectSystems {
+    Remove,
+    Apply,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Accessibility>()
+        .init_resource::<HitStopTimer>()
+        .add_observer(add_trauma)
+        .add_observer(kick_camera)
+        .add_observer(start_hit_stop)
+        .configure_sets(
+            PostUpdate,
+            (CameraEffectSystems::Remove, CameraEffectSystems::Apply)
+                .chain()
+                .in_set(CameraMovement),
+        )
+        .add_systems(Update, end_hit_stop)
+        .add_systems(
+            PostUpdate,
+            (
+                remove_camera_offset.in_set(CameraEffectSystems::Remove),
+                apply_camera_effects.in_set(CameraEffectSystems::Apply),
+            ),
+        );
+}
+
+fn add_trauma(
+    shake: On<CameraShake>,
+    accessibility: Res<Accessibility>,
+    mut cameras: Query<&mut CameraEffects, With<MainCamera>>,
+) {
+    if accessibility.reduced_motion {
+        return;
+    }
+    for mut effects in cameras.iter_mut() {
+        effects.trauma = (effects.trauma + shake.trauma).clamp(0.0, 1.0);
+    }
+}
+
+fn kick_camera(
+    kick: On<CameraKick>,
+    accessibility: Res<Accessibility>,
+    mut cameras: Query<&mut CameraEffects, With<MainCamera>>,
+) {
+    if accessibility.reduced_motion {
+        return;
+    }
+    for mut effects in cameras.iter_mut() {
+        effects.kick += kick.offset;
+    }
+}
+
+fn start_hit_stop(
+    hit_stop: On<HitStop>,
+    accessibility: Res<Accessibility>,
+    mut timer: ResMut<HitStopTimer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if accessibility.reduced_motion {
+        return;
+    }
+    // Overlapping stops extend rather than stack.
+    let remaining = timer.0.as_ref().map_or(0.0, Timer::remaining_secs);
+    timer.0 = Some(Timer::from_seconds(
+        hit_stop.secs.max(remaining),
+        TimerMode::Once,
+    ));
+    time.set_relative_speed(0.0);
+}
+
+fn end_hit_stop(
+    real_time: Res<Time<Real>>,
+    mut timer: ResMut<HitStopTimer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(stop) = timer.0.as_mut() else {
+        return;
+    };
+    if stop.tick(real_time.delta()).is_finished() {
+        timer.0 = None;
+        time.set_relative_speed(1.0);
+    }
+}
+
+fn remove_camera_offset(mut cameras: Query<(&mut CameraEffects, &mut Transform)>) {
+    for (mut effects, mut transform) in cameras.iter_mut() {
+        transform.translation -= effects.applied.extend(0.0);
+        transform.rotation = Quat::IDENTITY;
+        effects.applied = Vec2::ZERO;
+    }
+}
+
+/// Shake runs on real time so it keeps settling during a hit-stop.
+fn apply_camera_effects(
+    real_time: Res<Time<Real>>,
+    accessibility: Res<Accessibility>,
+    mut cameras: Query<(&mut CameraEffects, &mut Transform)>,
+) {
+    let delta = real_time.delta_secs();
+    let t = real_time.elapsed_secs();
+    for (mut effects, mut transform) in cameras.iter_mut() {
+        let effects = &mut *effects;
+        if accessibility.reduced_motion {
+            effects.trauma = 0.0;
+            effects.kick = Vec2::ZERO;
+            continue;
+        }
+
+        effects.trauma = (effects.trauma - effects.decay * delta).max(0.0);
+        effects.kick = smooth_damp(
+            effects.kick,
+            Vec2::ZERO,
+            &mut effects.kick_velocity,
+            effects.kick_smooth_time,
+            delta,
+        );
+
+        let shake = effects.trauma * effects.trauma;
+        let sample = t * effects.frequency;
+        let offset = effects.max_offset
+            * shake
+            * Vec2::new(value_noise(sample, 1.0), value_noise(sample, 2.0))
+            + effects.kick;
+        let angle = effects.max_angle * shake * value_noise(sample, 3.0);
+
+        transform.translation += offset.extend(0.0);
+        transform.rotation = Quat::from_rotation_z(angle);
+        effects.applied = offset;
+    }
+}
+
+/// Smooth 1D value noise in `-1.0..=1.0`. Different seeds give unrelated curves.
+fn value_noise(x: f32, seed: f32) -> f32 {
+    let hash =
+        |n: f32| ((n * 12.9898 + seed * 78.233).sin() * 43_758.547).rem_euclid(1.0) * 2.0 - 1.0;
+    let cell = x.floor();
+    let t = x - cell;
+    let eased = t * t * (3.0 - 2.0 * t);
+    hash(cell) + (hash(cell + 1.0) - hash(cell)) * eased
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_noise_stays_in_range_and_is_continuous() {
+        let mut previous = value_noise(0.0, 1.0);
+        for step in 1..2000 {
+            let sample = value_noise(step as f32 * 0.01, 1.0);
+            assert!((-1.0..=1.0).contains(&sample));
+            assert!((sample - previous).abs() < 0.1);
+            previous = sample;
+        }
+    }
+}
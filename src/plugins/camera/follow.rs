@@ -0,0 +1,370 @@
+//! Smooth follow camera. The camera tracks a focus point that only moves when the target leaves
+//! a deadzone, leads the target in the direction it is moving, and re-centers vertically only
+//! once the target lands.
+
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{math::VectorSpace, prelude::*};
+
+use crate::{components::Surroundings, events::FocusCamera};
+
+use super::{
+    CameraMovement, MainCamera,
+    bounds::{CameraConfiner, RoomTransition},
+    effects::CameraEffectSystems,
+    zoom::{CameraFrame, framed_area},
+};
+
+/// Makes an entity a candidate for the camera to follow. The highest priority wins, so raising
+/// one temporarily (as [`FocusCamera`] does) switches the camera over.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct CameraTarget {
+    pub priority: i32,
+}
+
+/// Follow settings and state for a camera.
+#[derive(Component, Clone, Debug)]
+pub struct CameraFollow {
+    /// Half-size of the box the target can move in without moving the focus.
+    pub deadzone: Vec2,
+    /// Roughly how long the camera takes to catch up with its goal.
+    pub smooth_time: f32,
+    /// How far ahead of a moving target the camera looks.
+    pub look_ahead: f32,
+    /// How quickly the look-ahead offset swings round when the target turns.
+    pub look_ahead_smooth_time: f32,
+    /// How far the target may leave the focus vertically while airborne before the camera
+    /// follows without waiting for a landing.
+    pub vertical_limit: f32,
+    pub focus: Vec2,
+    pub look_ahead_offset: Vec2,
+    pub velocity: Vec2,
+    pub look_ahead_velocity: Vec2,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            deadzone: Vec2::new(48.0, 32.0),
+            smooth_time: 0.25,
+            look_ahead: 96.0,
+            look_ahead_smooth_time: 0.5,
+            vertical_limit: 180.0,
+            focus: Vec2::ZERO,
+            look_ahead_offset: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            look_ahead_velocity: Vec2::ZERO,
+        }
+    }
+}
+
+/// A temporary priority boost added by [`FocusCamera`]. Remembers the target the entity had
+/// before, if any, so expiring hands it back instead of dropping it.
+#[derive(Component)]
+struct CameraFocus {
+    timer: Timer,
+    previous: Option<CameraTarget>,
+}
+
+const FOCUS_PRIORITY: i32 = 100;
+
+/// Speed below which the look-ahead settles back on the target.
+const LOOK_AHEAD_MIN_SPEED: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(focus_camera)
+        .add_systems(Update, expire_camera_focus)
+        .add_systems(
+            PostUpdate,
+            follow_target
+                .in_set(CameraMovement)
+                .after(CameraEffectSystems::Remove)
+                .before(CameraEffectSystems::Apply),
+        );
+}
+
+/// Critically damped spring towards `target`, stable for any frame time.
+pub fn smooth_damp<T: VectorSpace<Scalar = f32>>(
+    current: T,
+    target: T,
+    velocity: &mut T,
+    smooth_time: f32,
+    delta: f32,
+) -> T {
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let x = omega * delta;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = current - target;
+    let temp = (*velocity + change * omega) * delta;
+    *velocity = (*velocity - temp * omega) * decay;
+    target + (change + temp) * decay
+}
+
+fn focus_camera(
+    focus: On<FocusCamera>,
+    mut commands: Commands,
+    targets: Query<(Option<&CameraTarget>, Option<&CameraFocus>)>,
+) {
+    let Ok((target, focused)) = targets.get(focus.entity) else {
+        return;
+    };
+    // Refocusing keeps the target from before the first focus.
+    let previous = match focused {
+        Some(focused) => focused.previous,
+        None => target.copied(),
+    };
+    commands.entity(focus.entity).insert((
+        CameraTarget {
+            priority: FOCUS_PRIORITY,
+        },
+        CameraFocus {
+            timer: Timer::from_seconds(focus.secs, TimerMode::Once),
+            previous,
+        },
+    ));
+}
+
+fn expire_camera_focus(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut focused: Query<(Entity, &mut CameraFocus)>,
+) {
+    for (entity, mut focus) in focused.iter_mut() {
+        if !focus.timer.tick(time.delta()).is_finished() {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        match focus.previous {
+            Some(previous) => entity.insert(previous).remove::<CameraFocus>(),
+            None => entity.remove::<(CameraTarget, CameraFocus)>(),
+        };
+    }
+}
+
+fn follow_target(
+    time: Res<Time>,
+    mut cameras: Query<
+        (
+            &mut CameraFollow,
+            Option<&mut CameraConfiner>,
+            &Projection,
+            &mut Transform,
+        ),
+        With<MainCamera>,
+    >,
+    targets: Query<
+        (
+            &CameraTarget,
+            &Transform,
+            Option<&LinearVelocity>,
+            Option<&Surroundings>,
+            Has<CameraFocus>,
+        ),
+        Without<MainCamera>,
+    >,
+    frames: Query<(&CameraFrame, &Transform), Without<MainCamera>>,
+) {
+    let delta = time.delta_secs();
+    // Framing several entities overrides following a single one, except while something is
+    // being shown with `FocusCamera`.
+    let focused = targets
+        .iter()
+        .filter(|(.., focused)| *focused)
+        .max_by_key(|(target, ..)| target.priority);
+    let framed = framed_area(frames.iter()).filter(|_| focused.is_none());
+    let (target, velocity, grounded) = match (
+        framed,
+        focused.or_else(|| targets.iter().max_by_key(|(target, ..)| target.priority)),
+    ) {
+        (Some(area), _) => (area.center(), Vec2::ZERO, true),
+        (None, Some((_, transform, velocity, surroundings, _))) => (
+            transform.translation.xy(),
+            velocity.map_or(Vec2::ZERO, |velocity| velocity.0),
+            // Things that can't fall count as standing, so the camera centers on them.
+            surroundings.is_none_or(Surroundings::is_grounded),
+        ),
+        (None, None) => return,
+    };
+
+    for (mut follow, confiner, projection, mut transform) in cameras.iter_mut() {
+        let follow = &mut *follow;
+
+        if framed.is_some() {
+            // A framed group is kept centered; the zoom gives it room.
+            follow.focus = target;
+        } else {
+            // Push the focus along only as far as needed to keep the target in the deadzone.
+            let horizontal = target.x - follow.focus.x;
+            if horizontal.abs() > follow.deadzone.x {
+                follow.focus.x = target.x - follow.deadzone.x * horizontal.signum();
+            }
+            let vertical = target.y - follow.focus.y;
+            if grounded {
+                if vertical.abs() > follow.deadzone.y {
+                    follow.focus.y = target.y;
+                }
+            } else if vertical.abs() > follow.vertical_limit {
+                follow.focus.y = target.y - follow.vertical_limit * vertical.signum();
+            }
+        }
+
+        let look_ahead_goal = if velocity.x.abs() > LOOK_AHEAD_MIN_SPEED {
+            Vec2::X * velocity.x.signum() * follow.look_ahead
+        } else {
+            Vec2::ZERO
+        };
+        follow.look_ahead_offset = smooth_damp(
+            follow.look_ahead_offset,
+            look_ahead_goal,
+            &mut follow.look_ahead_velocity,
+            follow.look_ahead_smooth_time,
+            delta,
+        );
+
+        let current = transform.translation.xy();
+        let mut goal = follow.focus + follow.look_ahead_offset;
+        let position = match confiner {
+            Some(mut confiner) => {
+                let view_size = match projection {
+                    Projection::Orthographic(orthographic) => orthographic.area.size(),
+                    _ => Vec2::ZERO,
+                };
+                confine(
+                    &mut confiner,
+                    follow,
+                    target,
+                    current,
+                    &mut goal,
+                    view_size,
+                    delta,
+                )
+            }
+            None => None,
+        };
+        let position = position.unwrap_or_else(|| {
+            smooth_damp(
+                current,
+                goal,
+                &mut follow.velocity,
+                follow.smooth_time,
+                delta,
+            )
+        });
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// Clamps `goal` to the active room, switching rooms when the target crosses into another.
+/// Returns the camera position while a room-to-room pan is playing.
+fn confine(
+    confiner: &mut CameraConfiner,
+    follow: &mut CameraFollow,
+    target: Vec2,
+    current: Vec2,
+    goal: &mut Vec2,
+    view_size: Vec2,
+    delta: f32,
+) -> Option<Vec2> {
+    let room = confiner.room_containing(target);
+    if let Some(room) = room.filter(|&room| room != confiner.active) {
+        confiner.active = room;
+        confiner.transition = Some(RoomTransition {
+            from: current,
+            timer: Timer::from_seconds(confiner.transition_secs, TimerMode::Once),
+        });
+    }
+    *goal = confiner.clamp(*goal, view_size);
+
+    let transition = confiner.transition.as_mut()?;
+    transition.timer.tick(Duration::from_secs_f32(delta));
+    let t = transition.timer.fraction();
+    let position = transition.from.lerp(*goal, t * t * (3.0 - 2.0 * t));
+    if transition.timer.is_finished() {
+        confiner.transition = None;
+    }
+    // Start the follow from rest once the pan hands back control.
+    follow.velocity = Vec2::ZERO;
+    Some(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn smooth_damp_settles_without_overshooting() {
+        let target = Vec2::new(100.0, -50.0);
+        let mut position = Vec2::ZERO;
+        let mut velocity = Vec2::ZERO;
+        for _ in 0..240 {
+            position = smooth_damp(position, target, &mut velocity, 0.25, 1.0 / 60.0);
+            assert!(position.x <= target.x && position.y >= target.y);
+        }
+
+        assert!(position.distance(target) < 0.01);
+    }
+
+    #[test]
+    fn expired_focus_hands_back_the_previous_target() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin)).insert_resource(
+            TimeUpdateStrategy::ManualDuration(Duration::from_millis(250)),
+        );
+        let player = app.world_mut().spawn(CameraTarget { priority: 1 }).id();
+        let door = app.world_mut().spawn_empty().id();
+
+        for entity in [player, door] {
+            app.world_mut().trigger(FocusCamera { entity, secs: 0.5 });
+        }
+        assert_eq!(
+            app.world().get::<CameraTarget>(player).unwrap().priority,
+            FOCUS_PRIORITY
+        );
+        for _ in 0..4 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<CameraTarget>(player).unwrap().priority, 1);
+        assert!(app.world().get::<CameraTarget>(door).is_none());
+        assert!(app.world().get::<CameraFocus>(player).is_none());
+    }
+
+    #[test]
+    fn focus_takes_over_from_framed_players() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin));
+        let camera = app
+            .world_mut()
+            .spawn((
+                MainCamera,
+                CameraFollow::default(),
+                Projection::Orthographic(OrthographicProjection::default_2d()),
+                Transform::default(),
+            ))
+            .id();
+        for x in [-100.0, 100.0] {
+            app.world_mut().spawn((
+                CameraTarget { priority: 1 },
+                CameraFrame { radius: 10.0 },
+                Transform::from_xyz(x, 0.0, 0.0),
+            ));
+        }
+        let door = app
+            .world_mut()
+            .spawn(Transform::from_xyz(400.0, 0.0, 0.0))
+            .id();
+
+        app.world_mut().trigger(FocusCamera {
+            entity: door,
+            secs: 1.0,
+        });
+        app.update();
+
+        let follow = app.world().get::<CameraFollow>(camera).unwrap();
+        assert!(follow.focus.x > 100.0);
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+mod bounds;
+mod effects;
+mod follow;
+mod pixel;
+mod zoom;
+
+pub use bounds::{CameraBounds, CameraConfiner, clamp_to_room};
+pub use effects::CameraEffects;
+pub use follow::{CameraFollow, CameraTarget};
+pub use pixel::{OuterCamera, PixelCanvas, PixelPerfect, UiResolution};
+pub use zoom::{CameraFrame, CameraZoom};
+
+#[derive(Component)]
+#[require(Camera2d)]
+pub struct MainCamera;
+
+/// Everything that moves `MainCamera` in `PostUpdate`. Systems that place things relative to
+/// the camera should run after this set.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CameraMovement;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.configure_sets(
+        PostUpdate,
+        CameraMovement.before(TransformSystems::Propagate),
+    )
+    .add_plugins((follow::plugin, effects::plugin, pixel::plugin, zoom::plugin))
+    .add_systems(Startup, initialize_camera);
+}
+
+fn initialize_camera(mut commands: Commands) {
+    commands.spawn((
+        MainCamera,
+        CameraFollow::default(),
+        CameraEffects::default(),
+        CameraZoom::default(),
+    ));
+}
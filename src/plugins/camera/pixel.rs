@@ -0,0 +1,237 @@
+//! Optional pixel-perfect mode. `MainCamera` renders the world into a low-resolution image,
+//! which an outer camera draws to the window at the largest integer scale that fits, with
+//! black bars around it. The world camera moves in whole virtual pixels; the leftover
+//! sub-pixel offset is applied to the upscaled canvas so scrolling stays smooth.
+
+use bevy::{
+    camera::{RenderTarget, visibility::RenderLayers},
+    image::ImageSampler,
+    prelude::*,
+    render::render_resource::TextureFormat,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use super::{CameraEffects, CameraMovement, CameraZoom, MainCamera, effects::CameraEffectSystems};
+
+/// Which resolution the UI is laid out and rendered at in pixel-perfect mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UiResolution {
+    /// Crisp UI at the window's resolution, drawn over the upscaled canvas.
+    #[default]
+    Native,
+    /// UI rendered into the low-resolution image along with the world.
+    Virtual,
+}
+
+/// Pixel-perfect settings. Insert before the app starts to change them.
+#[derive(Resource, Clone, Debug)]
+pub struct PixelPerfect {
+    pub enabled: bool,
+    /// Size of the low-resolution render target in pixels.
+    pub resolution: UVec2,
+    /// World units visible across the width of the view, matching the non-pixel-perfect view.
+    pub world_width: f32,
+    pub ui: UiResolution,
+    /// Current integer upscale, updated with the window size.
+    pub upscale: f32,
+}
+
+impl Default for PixelPerfect {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            resolution: UVec2::new(640, 360),
+            world_width: 1280.0,
+            ui: UiResolution::Native,
+            upscale: 1.0,
+        }
+    }
+}
+
+impl PixelPerfect {
+    /// World units covered by one virtual pixel.
+    pub fn pixel_size(&self) -> f32 {
+        self.world_width / self.resolution.x as f32
+    }
+
+    /// Converts a position in window coordinates to `MainCamera`'s viewport, which is the
+    /// low-resolution image while pixel-perfect mode is on.
+    pub fn window_to_viewport(&self, position: Vec2, window_size: Vec2) -> Vec2 {
+        if !self.enabled {
+            return position;
+        }
+        (position - window_size * 0.5) / self.upscale + self.resolution.as_vec2() * 0.5
+    }
+
+    /// Converts a UI position to `MainCamera`'s viewport. Native UI is laid out over the
+    /// window, virtual UI already shares the camera's viewport.
+    pub fn ui_to_viewport(&self, position: Vec2, window_size: Vec2) -> Vec2 {
+        match self.ui {
+            UiResolution::Native => self.window_to_viewport(position, window_size),
+            UiResolution::Virtual => position,
+        }
+    }
+}
+
+/// The sprite showing the low-resolution image in the window.
+#[derive(Component)]
+pub struct PixelCanvas;
+
+/// Renders [`PixelCanvas`] to the window.
+#[derive(Component)]
+pub struct OuterCamera;
+
+const CANVAS_LAYER: usize = 1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PixelPerfect>()
+        .add_systems(PostStartup, setup_pixel_perfect)
+        .add_systems(Update, fit_canvas)
+        .add_systems(
+            PostUpdate,
+            snap_camera
+                .in_set(CameraMovement)
+                .after(CameraEffectSystems::Apply),
+        );
+}
+
+fn setup_pixel_perfect(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    settings: Res<PixelPerfect>,
+    cameras: Query<Entity, With<MainCamera>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let Ok(camera) = cameras.single() else {
+        return;
+    };
+
+    let mut image = Image::new_target_texture(
+        settings.resolution.x,
+        settings.resolution.y,
+        TextureFormat::bevy_default(),
+        None,
+    );
+    image.sampler = ImageSampler::nearest();
+    let image = images.add(image);
+
+    commands.entity(camera).insert((
+        Camera {
+            // Render the world before the outer camera draws it.
+            order: -1,
+            ..default()
+        },
+        RenderTarget::from(image.clone()),
+        Msaa::Off,
+        Projection::Orthographic(OrthographicProjection {
+            scale: settings.pixel_size(),
+            ..OrthographicProjection::default_2d()
+        }),
+    ));
+    if settings.ui == UiResolution::Virtual {
+        commands.entity(camera).insert(IsDefaultUiCamera);
+    }
+
+    commands.spawn((
+        Name::new("PixelCanvas"),
+        PixelCanvas,
+        Sprite::from_image(image),
+        RenderLayers::layer(CANVAS_LAYER),
+    ));
+    let mut outer = commands.spawn((
+        Name::new("OuterCamera"),
+        OuterCamera,
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
+            ..default()
+        },
+        Msaa::Off,
+        RenderLayers::layer(CANVAS_LAYER),
+    ));
+    if settings.ui == UiResolution::Native {
+        outer.insert(IsDefaultUiCamera);
+    }
+}
+
+/// Scales the canvas by the largest whole factor that fits the window.
+fn fit_canvas(
+    mut resized: MessageReader<WindowResized>,
+    mut settings: ResMut<PixelPerfect>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut canvases: Query<&mut Transform, With<PixelCanvas>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    // Also fit once on the first frame, before any resize arrives.
+    let first_fit = settings.is_added();
+    if resized.read().last().is_none() && !first_fit {
+        return;
+    }
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let fit = window.size() / settings.resolution.as_vec2();
+    settings.upscale = fit.x.min(fit.y).floor().max(1.0);
+    for mut transform in canvases.iter_mut() {
+        transform.scale = Vec3::splat(settings.upscale);
+    }
+}
+
+/// Rounds the world camera to whole virtual pixels and shifts the canvas by the remainder.
+/// The rounding is recorded as part of the applied camera offset, so it is undone before the
+/// follow runs next frame.
+fn snap_camera(
+    settings: Res<PixelPerfect>,
+    mut cameras: Query<(&mut Transform, &mut CameraEffects, Option<&CameraZoom>), With<MainCamera>>,
+    mut canvases: Query<&mut Transform, (With<PixelCanvas>, Without<MainCamera>)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    for (mut transform, mut effects, zoom) in cameras.iter_mut() {
+        // Zooming changes how much world one virtual pixel covers.
+        let pixel = settings.pixel_size() * zoom.map_or(1.0, |zoom| zoom.current);
+        let position = transform.translation.xy();
+        let snapped = (position / pixel).round() * pixel;
+        transform.translation.x = snapped.x;
+        transform.translation.y = snapped.y;
+        effects.applied += snapped - position;
+
+        // The canvas moves against the camera, in window pixels.
+        let remainder = (position - snapped) / pixel * settings.upscale;
+        for mut canvas in canvases.iter_mut() {
+            canvas.translation.x = -remainder.x;
+            canvas.translation.y = -remainder.y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_positions_map_into_the_letterboxed_canvas() {
+        let settings = PixelPerfect {
+            enabled: true,
+            upscale: 2.0,
+            ..default()
+        };
+        // A 1400x760 window shows the 640x360 canvas at 2x with bars around it.
+        let window_size = Vec2::new(1400.0, 760.0);
+
+        assert_eq!(
+            settings.window_to_viewport(window_size * 0.5, window_size),
+            Vec2::new(320.0, 180.0)
+        );
+        assert_eq!(
+            settings.window_to_viewport(Vec2::new(60.0, 20.0), window_size),
+            Vec2::ZERO
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Smoothed orthographic zoom. The camera eases towards a chosen zoom, or, while any
+//! [`CameraFrame`] entities exist, towards the zoom that fits all of them on screen.
+
+use bevy::prelude::*;
+
+use super::{
+    CameraMovement, MainCamera, PixelPerfect, effects::CameraEffectSystems, follow::smooth_damp,
+};
+
+/// Zoom settings and state for a camera. Zoom multiplies the projection scale, so 1 is the
+/// normal view and values above 1 show more of the world.
+#[derive(Component, Clone, Debug)]
+pub struct CameraZoom {
+    /// Zoom to settle at while nothing is framed.
+    pub zoom: f32,
+    pub min: f32,
+    pub max: f32,
+    /// World units kept visible around the framed area on each side.
+    pub padding: Vec2,
+    /// Roughly how long the zoom takes to settle.
+    pub smooth_time: f32,
+    pub current: f32,
+    pub velocity: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            min: 0.5,
+            max: 2.0,
+            padding: Vec2::new(160.0, 120.0),
+            smooth_time: 0.4,
+            current: 1.0,
+            velocity: 0.0,
+        }
+    }
+}
+
+impl CameraZoom {
+    /// The zoom that fits `area` plus padding into a view of `view_size` at zoom 1.
+    pub fn fit(&self, area: Rect, view_size: Vec2) -> f32 {
+        let needed = (area.size() + self.padding * 2.0) / view_size;
+        needed.x.max(needed.y).clamp(self.min, self.max)
+    }
+}
+
+/// Keeps an entity on screen. While any exist, the camera centers on all of them and zooms to
+/// fit.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct CameraFrame {
+    /// Extra room around this entity, on top of the camera's padding.
+    pub radius: f32,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        zoom_camera
+            .in_set(CameraMovement)
+            .after(CameraEffectSystems::Remove)
+            .before(CameraEffectSystems::Apply),
+    );
+}
+
+/// The smallest rectangle holding every framed entity, if there are any.
+pub(super) fn framed_area<'a>(
+    frames: impl Iterator<Item = (&'a CameraFrame, &'a Transform)>,
+) -> Option<Rect> {
+    frames
+        .map(|(frame, transform)| {
+            Rect::from_center_half_size(transform.translation.xy(), Vec2::splat(frame.radius))
+        })
+        .reduce(|area, rect| area.union(rect))
+}
+
+fn zoom_camera(
+    time: Res<Time>,
+    settings: Res<PixelPerfect>,
+    mut cameras: Query<(&mut CameraZoom, &mut Projection), With<MainCamera>>,
+    frames: Query<(&CameraFrame, &Transform), Without<MainCamera>>,
+) {
+    // Pixel-perfect mode sets its own base scale for the low-resolution view.
+    let base_scale = if settings.enabled {
+        settings.pixel_size()
+    } else {
+        1.0
+    };
+    let framed = framed_area(frames.iter());
+
+    for (mut zoom, mut projection) in cameras.iter_mut() {
+        let Projection::Orthographic(orthographic) = &*projection else {
+            continue;
+        };
+        let view_size = orthographic.area.size() / orthographic.scale * base_scale;
+        let goal = match framed {
+            Some(area) if view_size.min_element() > 0.0 => zoom.fit(area, view_size),
+            _ => zoom.zoom.clamp(zoom.min, zoom.max),
+        };
+
+        let zoom = &mut *zoom;
+        zoom.current = smooth_damp(
+            zoom.current,
+            goal,
+            &mut zoom.velocity,
+            zoom.smooth_time,
+            time.delta_secs(),
+        );
+        let scale = base_scale * zoom.current;
+        if (orthographic.scale - scale).abs() > f32::EPSILON {
+            if let Projection::Orthographic(orthographic) = &mut *projection {
+                orthographic.scale = scale;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn framing_fits_every_target_within_the_zoom_limits() {
+        let zoom = CameraZoom {
+            padding: Vec2::splat(50.0),
+            ..default()
+        };
+        let view = Vec2::new(1280.0, 720.0);
+        let frame = CameraFrame { radius: 10.0 };
+        let a = Transform::from_xyz(-600.0, 0.0, 0.0);
+        let b = Transform::from_xyz(600.0, 100.0, 0.0);
+
+        let area = framed_area([(&frame, &a), (&frame, &b)].into_iter()).unwrap();
+        assert_eq!(area, Rect::new(-610.0, -10.0, 610.0, 110.0));
+        // 1220 wide plus 100 padding doesn't fit 1280, so zoom out a little.
+        assert!((zoom.fit(area, view) - 1320.0 / 1280.0).abs() < 1e-5);
+
+        let close = framed_area([(&frame, &a)].into_iter()).unwrap();
+        assert_eq!(zoom.fit(close, view), zoom.min);
+        let far = Rect::new(-5000.0, 0.0, 5000.0, 0.0);
+        assert_eq!(zoom.fit(far, view), zoom.max);
+    }
+}
@@ -1,6 +1,38 @@
 use bevy::app::App;
 use bevy::diagnostic::LogDiagnosticsPlugin;
+use bevy::prelude::*;
+
+use crate::{
+    plugins::{game::REPLAY_KEY, menus::MenuAction},
+    resources::{InputReplay, Replay},
+    storage,
+};
 
 pub(crate) fn plugin(app: &mut App) {
-    app.add_plugins(LogDiagnosticsPlugin::default());
+    app.add_plugins(LogDiagnosticsPlugin::default())
+        .add_systems(Update, replay_hotkeys);
+}
+
+/// F6 restarts the level replaying the run so far, or the last saved run if nothing has been
+/// recorded yet. F5 restarts it on live input again.
+fn replay_hotkeys(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut input_replay: ResMut<InputReplay>,
+) {
+    if keys.just_pressed(KeyCode::F5) && input_replay.is_replaying() {
+        *input_replay = InputReplay::default();
+        commands.trigger(MenuAction::Retry);
+    } else if keys.just_pressed(KeyCode::F6) {
+        let replay = match &*input_replay {
+            InputReplay::Recording(replay) if !replay.ticks.is_empty() => Some(replay.clone()),
+            _ => storage::load(REPLAY_KEY).and_then(|text| Replay::from_text(&text)),
+        };
+        let Some(replay) = replay else {
+            warn!("No replay to play");
+            return;
+        };
+        *input_replay = InputReplay::play(replay);
+        commands.trigger(MenuAction::Retry);
+    }
 }
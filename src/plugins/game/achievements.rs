@@ -0,0 +1,107 @@
+//! Achievements unlocked by gameplay events, announced with a short toast.
+
+use bevy::{platform::collections::HashSet, prelude::*, text::FontSmoothing};
+
+use crate::{
+    components::{Collectible, CollectibleKind, PickedUp},
+    events::CoinCollected,
+    plugins::fonts::FontAssets,
+};
+
+use super::constants::{HUD_TEXT_COLOR, TOAST_SECONDS};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Achievement {
+    FirstCoin,
+    CoinSweep,
+}
+
+impl Achievement {
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::FirstCoin => "First Coin",
+            Self::CoinSweep => "Coin Sweep",
+        }
+    }
+}
+
+/// Achievements unlocked so far. They carry over between runs.
+#[derive(Resource, Default, Debug)]
+pub struct Achievements {
+    pub unlocked: HashSet<Achievement>,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AchievementUnlocked(pub Achievement);
+
+#[derive(Component)]
+pub struct Toast {
+    pub timer: Timer,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Achievements>()
+        .add_observer(check_coin_achievements)
+        .add_observer(show_achievement_toast)
+        .add_systems(Update, expire_toasts);
+}
+
+fn unlock(commands: &mut Commands, achievements: &mut Achievements, achievement: Achievement) {
+    if achievements.unlocked.insert(achievement) {
+        commands.trigger(AchievementUnlocked(achievement));
+    }
+}
+
+fn check_coin_achievements(
+    _: On<CoinCollected>,
+    mut commands: Commands,
+    mut achievements: ResMut<Achievements>,
+    remaining: Query<&Collectible, Without<PickedUp>>,
+) {
+    unlock(&mut commands, &mut achievements, Achievement::FirstCoin);
+    if !remaining
+        .iter()
+        .any(|collectible| collectible.kind == CollectibleKind::COIN)
+    {
+        unlock(&mut commands, &mut achievements, Achievement::CoinSweep);
+    }
+}
+
+fn show_achievement_toast(
+    unlocked: On<AchievementUnlocked>,
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+) {
+    info!("Achievement unlocked: {}", unlocked.0.title());
+    commands.spawn((
+        Name::new("AchievementToast"),
+        Toast {
+            timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(24.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Text::new(format!("Achievement: {}", unlocked.0.title())),
+            TextFont {
+                font: fonts.default.clone(),
+                font_size: 36.0,
+                font_smoothing: FontSmoothing::None,
+                ..default()
+            },
+            TextColor(HUD_TEXT_COLOR),
+        )],
+    ));
+}
+
+fn expire_toasts(mut commands: Commands, time: Res<Time>, mut toasts: Query<(Entity, &mut Toast)>) {
+    for (entity, mut toast) in toasts.iter_mut() {
+        if toast.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Idle animation for collectibles and the flight of collected coins into the HUD.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    components::{CoinFlight, CoinSlot, CollectibleKind, IdleAnimation},
+    events::CoinCollected,
+    plugins::camera::{MainCamera, PixelPerfect},
+    resources::{CoinState, CollectibleKinds},
+    states::GameState,
+};
+
+use super::constants::{
+    COIN_FLIGHT_SECONDS, COIN_FLIGHT_Z, IDLE_BOB_HEIGHT, IDLE_BOB_SPEED, IDLE_HIGHLIGHT_COLOR,
+    IDLE_SHIMMER, IDLE_SPIN_SPEED,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(launch_coin_flight).add_systems(
+        Update,
+        (animate_idle, fly_coins).run_if(in_state(GameState::Playing)),
+    );
+}
+
+fn animate_idle(
+    time: Res<Time>,
+    mut collectibles: Query<(&IdleAnimation, &mut Transform, &mut Sprite)>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (idle, mut transform, mut sprite) in collectibles.iter_mut() {
+        let t = elapsed + idle.phase;
+        transform.translation.y = (t * IDLE_BOB_SPEED).sin() * IDLE_BOB_HEIGHT;
+        // Squash on X to fake a spin around the vertical axis.
+        transform.scale.x = (t * IDLE_SPIN_SPEED).cos();
+        let shimmer = (t * IDLE_SPIN_SPEED * 2.0).sin() * 0.5 + 0.5;
+        sprite.color = idle
+            .base_color
+            .mix(&IDLE_HIGHLIGHT_COLOR, shimmer * IDLE_SHIMMER);
+    }
+}
+
+/// Sends a copy of the collected coin towards the next empty slot. Without a slot to aim for,
+/// as in the numeric counter, the coin lands straight away.
+fn launch_coin_flight(
+    collected: On<CoinCollected>,
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    kinds: Res<CollectibleKinds>,
+    mut coin_state: ResMut<CoinState>,
+    flights: Query<&CoinFlight>,
+    slots: Query<&CoinSlot>,
+) {
+    let in_flight: usize = flights.iter().map(|flight| flight.value as usize).sum();
+    let slot = coin_state.arrived + in_flight;
+    let Some(info) = kinds.get(CollectibleKind::COIN) else {
+        coin_state.arrived += collected.value as usize;
+        return;
+    };
+    if !slots.iter().any(|coin_slot| coin_slot.index == slot) {
+        coin_state.arrived += collected.value as usize;
+        return;
+    }
+
+    commands.spawn((
+        Name::new("CoinFlight"),
+        CoinFlight {
+            start: collected.position,
+            slot,
+            value: collected.value,
+            timer: Timer::from_seconds(COIN_FLIGHT_SECONDS, TimerMode::Once),
+        },
+        Sprite {
+            image: info
+                .texture
+                .map(|path| assets.load(path))
+                .unwrap_or_default(),
+            color: info.color,
+            custom_size: Some(info.size),
+            ..default()
+        },
+        Transform::from_translation(collected.position.extend(COIN_FLIGHT_Z)),
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+/// Eases each flying coin towards its slot. The slot's UI position is converted back into
+/// world space through `MainCamera` every frame, so the target stays put if the camera moves.
+fn fly_coins(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut coin_state: ResMut<CoinState>,
+    pixel_perfect: Res<PixelPerfect>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    slots: Query<(&CoinSlot, &ComputedNode, &UiGlobalTransform)>,
+    mut flights: Query<(Entity, &mut CoinFlight, &mut Transform)>,
+) {
+    let camera = cameras.single().ok();
+    let window_size = windows.single().map_or(Vec2::ZERO, Window::size);
+    for (entity, mut flight, mut transform) in flights.iter_mut() {
+        let target = camera.and_then(|(camera, camera_transform)| {
+            let (_, node, ui_transform) = slots
+                .iter()
+                .find(|(coin_slot, ..)| coin_slot.index == flight.slot)?;
+            // UI transforms are in physical pixels; the viewport works in logical ones.
+            let viewport = pixel_perfect.ui_to_viewport(
+                ui_transform.translation * node.inverse_scale_factor(),
+                window_size,
+            );
+            camera.viewport_to_world_2d(camera_transform, viewport).ok()
+        });
+
+        let finished = flight.timer.tick(time.delta()).is_finished();
+        let Some(target) = target.filter(|_| !finished) else {
+            coin_state.arrived += flight.value as usize;
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let t = flight.timer.fraction();
+        let eased = t * t * (3.0 - 2.0 * t);
+        let position = flight.start.lerp(target, eased);
+        transform.translation = position.extend(COIN_FLIGHT_Z);
+        transform.scale = Vec3::splat(1.0 - 0.4 * eased);
+    }
+}
@@ -0,0 +1,391 @@
+//! Collectibles of every kind. Pickup only announces [`Collected`]; what a kind does is up to
+//! its own observer.
+
+use avian2d::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*, text::FontSmoothing};
+
+use crate::{
+    components::{
+        Collectible, CollectibleCounter, CollectibleKind, Health, IdleAnimation, KeyId, PickedUp,
+        Player, PlayerNumber, PowerUp,
+    },
+    events::{CoinCollected, Collected},
+    plugins::fonts::FontAssets,
+    resources::{CoinState, CollectedCounts, CollectibleKindInfo, CollectibleKinds},
+    states::GameState,
+};
+
+use super::{
+    constants::{
+        COIN_POINTS, COIN_RADIUS, COIN_SIZE, GEM_COLOR, GEM_POINTS, GEM_SIZE, HEALTH_PICKUP_COLOR,
+        HEALTH_PICKUP_POINTS, HEALTH_PICKUP_SIZE, HUD_TEXT_COLOR, KEY_COLOR, KEY_POINTS, KEY_SIZE,
+        POWER_UP_COLOR, POWER_UP_POINTS, POWER_UP_SIZE,
+    },
+    level::CollectibleSpec,
+    power_ups::power_up_color,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CollectibleKinds>()
+        .init_resource::<CollectedCounts>()
+        .register_collectible(
+            CollectibleKind::COIN,
+            CollectibleKindInfo {
+                label: "Coins",
+                texture: Some("textures/ferris.png"),
+                color: Color::WHITE,
+                size: COIN_SIZE,
+                radius: COIN_RADIUS,
+                sound: Some("audio/coin.wav"),
+                // Shown as the slot counter rather than a line of text.
+                hud_counter: true,
+                points: COIN_POINTS,
+            },
+        )
+        .register_collectible(
+            CollectibleKind::GEM,
+            CollectibleKindInfo {
+                label: "Gems",
+                texture: None,
+                color: GEM_COLOR,
+                size: GEM_SIZE,
+                radius: GEM_SIZE.x * 0.5,
+                sound: Some("audio/gem.wav"),
+                hud_counter: true,
+                points: GEM_POINTS,
+            },
+        )
+        .register_collectible(
+            CollectibleKind::HEALTH,
+            CollectibleKindInfo {
+                label: "Hearts",
+                texture: None,
+                color: HEALTH_PICKUP_COLOR,
+                size: HEALTH_PICKUP_SIZE,
+                radius: HEALTH_PICKUP_SIZE.x * 0.5,
+                sound: Some("audio/health.wav"),
+                hud_counter: true,
+                points: HEALTH_PICKUP_POINTS,
+            },
+        )
+        .register_collectible(
+            CollectibleKind::KEY,
+            CollectibleKindInfo {
+                label: "Keys",
+                texture: None,
+                color: KEY_COLOR,
+                size: KEY_SIZE,
+                radius: KEY_SIZE.y * 0.5,
+                sound: Some("audio/key.wav"),
+                hud_counter: true,
+                points: KEY_POINTS,
+            },
+        )
+        .register_collectible(
+            CollectibleKind::POWER_UP,
+            CollectibleKindInfo {
+                label: "Power-ups",
+                texture: None,
+                color: POWER_UP_COLOR,
+                size: POWER_UP_SIZE,
+                radius: POWER_UP_SIZE.x * 0.5,
+                sound: Some("audio/power_up.wav"),
+                hud_counter: true,
+                points: POWER_UP_POINTS,
+            },
+        )
+        .add_observer(count_collected)
+        .add_observer(play_pickup_sound)
+        .add_observer(play_coin_sound)
+        .add_observer(count_spawned_coins)
+        .add_observer(announce_coin_collected)
+        .add_observer(add_coins)
+        .add_observer(heal_collector)
+        .add_systems(
+            FixedUpdate,
+            collect_collectibles.run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            update_collectible_counters.run_if(in_state(GameState::Playing)),
+        );
+}
+
+pub trait RegisterCollectibleExt {
+    /// Adds or replaces a collectible kind. Pair it with an observer of [`Collected`] that
+    /// filters on `kind` to give the kind an effect.
+    fn register_collectible(
+        &mut self,
+        kind: CollectibleKind,
+        info: CollectibleKindInfo,
+    ) -> &mut Self;
+}
+
+impl RegisterCollectibleExt for App {
+    fn register_collectible(
+        &mut self,
+        kind: CollectibleKind,
+        info: CollectibleKindInfo,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<CollectibleKinds>()
+            .register(kind, info);
+        self
+    }
+}
+
+pub fn spawn_collectibles(
+    commands: &mut Commands,
+    assets: &AssetServer,
+    kinds: &CollectibleKinds,
+    collectibles: &[CollectibleSpec],
+) {
+    for spec in collectibles {
+        let Some(info) = kinds.get(spec.kind) else {
+            warn!("Skipping collectible of unregistered kind {:?}", spec.kind);
+            continue;
+        };
+        let color = spec.power_up.map(power_up_color).unwrap_or(info.color);
+        let mut collectible = commands.spawn((
+            Name::new(info.label),
+            Collectible::new(spec.kind, spec.value),
+            DespawnOnExit(GameState::Playing),
+            Transform::from_translation(spec.position.extend(1.0)),
+            Visibility::default(),
+            RigidBody::Static,
+            Collider::circle(info.radius),
+            Sensor,
+            children![(
+                IdleAnimation {
+                    base_color: color,
+                    phase: spec.position.x * 0.01,
+                },
+                Sprite {
+                    image: info
+                        .texture
+                        .map(|path| assets.load(path))
+                        .unwrap_or_default(),
+                    color,
+                    custom_size: Some(info.size),
+                    ..default()
+                },
+            )],
+        ));
+        if let Some(kind) = spec.power_up {
+            collectible.insert(PowerUp(kind));
+        }
+        if let Some(key) = spec.key {
+            collectible.insert(KeyId(key));
+        }
+    }
+}
+
+pub fn spawn_collectible_counters(
+    commands: &mut Commands,
+    fonts: &FontAssets,
+    kinds: &CollectibleKinds,
+) {
+    let root = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(84.0),
+                right: Val::Px(48.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                ..default()
+            },
+            Name::new("CollectibleCounters"),
+            DespawnOnExit(GameState::Playing),
+        ))
+        .id();
+
+    // Coins count in their own slots, next to the lives.
+    for (kind, info) in kinds
+        .iter()
+        .filter(|(kind, info)| info.hud_counter && *kind != CollectibleKind::COIN)
+    {
+        commands.spawn((
+            Text::new(format!("{}: 0", info.label)),
+            TextFont {
+                font: fonts.default.clone(),
+                font_size: 32.0,
+                font_smoothing: FontSmoothing::None,
+                ..default()
+            },
+            TextColor(HUD_TEXT_COLOR),
+            CollectibleCounter(kind),
+            ChildOf(root),
+        ));
+    }
+}
+
+pub fn collect_collectibles(
+    mut commands: Commands,
+    mut picked_this_frame: Local<EntityHashSet>,
+    players: Query<(Entity, &CollidingEntities), With<Player>>,
+    collectibles: Query<(&Collectible, &Transform), Without<PickedUp>>,
+) {
+    picked_this_frame.clear();
+    for (player, colliding) in players.iter() {
+        for &entity in colliding.iter() {
+            let Ok((collectible, transform)) = collectibles.get(entity) else {
+                continue;
+            };
+            // `PickedUp` only lands when commands apply, so also guard against two players
+            // reaching the same item within this run.
+            if !picked_this_frame.insert(entity) {
+                continue;
+            }
+            commands.entity(entity).insert(PickedUp);
+            commands.trigger(Collected {
+                collector: player,
+                item: entity,
+                kind: collectible.kind,
+                value: collectible.value,
+                position: transform.translation.xy(),
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn count_collected(collected: On<Collected>, mut counts: ResMut<CollectedCounts>) {
+    *counts.0.entry(collected.kind).or_default() += collected.value;
+}
+
+fn play_sound(commands: &mut Commands, assets: &AssetServer, sound: Option<&'static str>) {
+    let Some(sound) = sound else {
+        return;
+    };
+    commands.spawn((
+        AudioPlayer::new(assets.load(sound)),
+        PlaybackSettings::DESPAWN,
+    ));
+}
+
+/// Coins play theirs from [`play_coin_sound`].
+fn play_pickup_sound(
+    collected: On<Collected>,
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    kinds: Res<CollectibleKinds>,
+) {
+    if collected.kind == CollectibleKind::COIN {
+        return;
+    }
+    let sound = kinds.get(collected.kind).and_then(|info| info.sound);
+    play_sound(&mut commands, &assets, sound);
+}
+
+fn play_coin_sound(
+    _: On<CoinCollected>,
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    kinds: Res<CollectibleKinds>,
+) {
+    let sound = kinds.get(CollectibleKind::COIN).and_then(|info| info.sound);
+    play_sound(&mut commands, &assets, sound);
+}
+
+/// Derives the level's coin total from what is actually spawned, including coins spawned after
+/// the level starts.
+pub fn count_spawned_coins(
+    add: On<Add, Collectible>,
+    collectibles: Query<&Collectible>,
+    mut coin_state: ResMut<CoinState>,
+) {
+    let Ok(collectible) = collectibles.get(add.entity) else {
+        return;
+    };
+    if collectible.kind == CollectibleKind::COIN {
+        coin_state.total += collectible.value as usize;
+    }
+}
+
+/// The coin kind's pickup effect: hand off to the coin-specific observers.
+pub fn announce_coin_collected(collected: On<Collected>, mut commands: Commands) {
+    if collected.kind != CollectibleKind::COIN {
+        return;
+    }
+    commands.trigger(CoinCollected {
+        entity: collected.item,
+        collector: collected.collector,
+        position: collected.position,
+        value: collected.value,
+    });
+}
+
+pub fn add_coins(
+    collected: On<CoinCollected>,
+    mut coin_state: ResMut<CoinState>,
+    players: Query<&PlayerNumber>,
+) {
+    let player = players.get(collected.collector).ok().map(|number| number.0);
+    coin_state.add(player, collected.value as usize);
+}
+
+fn heal_collector(collected: On<Collected>, mut healths: Query<&mut Health>) {
+    if collected.kind != CollectibleKind::HEALTH {
+        return;
+    }
+    let Ok(mut health) = healths.get_mut(collected.collector) else {
+        return;
+    };
+    health.current = (health.current + collected.value).min(health.max);
+}
+
+fn update_collectible_counters(
+    counts: Res<CollectedCounts>,
+    kinds: Res<CollectibleKinds>,
+    mut counters: Query<(&CollectibleCounter, &mut Text)>,
+) {
+    if !counts.is_changed() {
+        return;
+    }
+
+    for (counter, mut text) in counters.iter_mut() {
+        let Some(info) = kinds.get(counter.0) else {
+            continue;
+        };
+        text.0 = format!("{}: {}", info.label, counts.get(counter.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_has_a_sound_and_counters_follow_the_hud_flag() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin));
+        let mut kinds = app.world().resource::<CollectibleKinds>().clone();
+        assert!(kinds.iter().all(|(_, info)| info.sound.is_some()));
+
+        let mut hidden = kinds.get(CollectibleKind::GEM).unwrap().clone();
+        hidden.hud_counter = false;
+        kinds.register(CollectibleKind::GEM, hidden);
+        let fonts = FontAssets {
+            default: Handle::default(),
+        };
+        let mut commands = app.world_mut().commands();
+        spawn_collectible_counters(&mut commands, &fonts, &kinds);
+        app.world_mut().flush();
+
+        let counters: Vec<_> = app
+            .world_mut()
+            .query::<&CollectibleCounter>()
+            .iter(app.world())
+            .map(|counter| counter.0)
+            .collect();
+        assert_eq!(
+            counters,
+            [
+                CollectibleKind::HEALTH,
+                CollectibleKind::KEY,
+                CollectibleKind::POWER_UP
+            ]
+        );
+    }
+}
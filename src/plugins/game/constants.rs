@@ -1,13 +1,91 @@
 use bevy::prelude::{Color, Vec2};
 
+use crate::components::ProjectileSpec;
+
 pub const PLAYER_SPEED: f32 = 240.0;
 pub const JUMP_SPEED: f32 = 520.0;
 pub const PLAYER_SIZE: Vec2 = Vec2::splat(64.0);
+pub const PLAYER_MAX_HEALTH: u32 = 3;
+pub const PLAYER_LIVES: u32 = 3;
+pub const MAX_PLAYERS: usize = 4;
+/// Tints that tell local players apart, by player number.
+pub const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::WHITE,
+    Color::srgb(0.5, 0.8, 1.0),
+    Color::srgb(1.0, 0.6, 0.5),
+    Color::srgb(0.6, 1.0, 0.5),
+];
+pub const INVULNERABILITY_SECONDS: f32 = 1.0;
+pub const STAGGER_SECONDS: f32 = 0.3;
+
+pub const SPEED_BOOST_SECONDS: f32 = 6.0;
+pub const SPEED_BOOST_PER_STACK: f32 = 0.3;
+pub const SPEED_BOOST_MAX_STACKS: u32 = 3;
+pub const HIGH_JUMP_SECONDS: f32 = 8.0;
+pub const HIGH_JUMP_MULTIPLIER: f32 = 1.35;
+pub const LOW_GRAVITY_SECONDS: f32 = 8.0;
+pub const LOW_GRAVITY_SCALE: f32 = 0.5;
+pub const MAGNET_SECONDS: f32 = 10.0;
+pub const MAGNET_RADIUS: f32 = 220.0;
+pub const MAGNET_PULL_SPEED: f32 = 320.0;
+pub const SHIELD_SECONDS: f32 = 12.0;
 
 pub const PLATFORM_SIZE: Vec2 = Vec2::new(520.0, 28.0);
 pub const SMALL_PLATFORM_SIZE: Vec2 = Vec2::new(180.0, 20.0);
 
-pub const COIN_SLOTS: usize = 3;
+pub const DOOR_SIZE: Vec2 = Vec2::new(24.0, 120.0);
+pub const DOOR_OPEN_SPEED: f32 = 2.0;
+pub const DOOR_FOCUS_SECONDS: f32 = 1.5;
+pub const SWITCH_SIZE: Vec2 = Vec2::new(16.0, 32.0);
+pub const PRESSURE_PLATE_SIZE: Vec2 = Vec2::new(48.0, 8.0);
+pub const LIFT_SPEED: f32 = 90.0;
+pub const GOAL_SIZE: Vec2 = Vec2::new(32.0, 64.0);
+
+/// Background layers sit between these, behind every gameplay sprite (z >= 0).
+pub const PARALLAX_Z_FAR: f32 = -100.0;
+pub const PARALLAX_Z_NEAR: f32 = -10.0;
+
+pub const LAVA_SIZE: Vec2 = Vec2::new(1280.0, 40.0);
+pub const SPIKES_SIZE: Vec2 = Vec2::new(60.0, 16.0);
+pub const SPIKES_DAMAGE: u32 = 1;
+pub const SPIKES_KNOCKBACK: f32 = 420.0;
+pub const CRUSHER_SIZE: Vec2 = Vec2::new(64.0, 40.0);
+pub const CRUSHER_HITBOX_HEIGHT: f32 = 8.0;
+
+pub const ENEMY_SIZE: Vec2 = Vec2::new(48.0, 40.0);
+pub const ENEMY_SPEED: f32 = 90.0;
+pub const ENEMY_CHASE_SPEED: f32 = 160.0;
+pub const ENEMY_CONTACT_DAMAGE: u32 = 1;
+pub const ENEMY_KNOCKBACK: Vec2 = Vec2::new(360.0, 260.0);
+pub const STOMP_BOUNCE_SPEED: f32 = 420.0;
+pub const STOMP_TOLERANCE: f32 = 8.0;
+
+pub const PROJECTILE_POOL_SIZE: usize = 32;
+pub const PROJECTILE_Z: f32 = 2.0;
+pub const PLAYER_SHOOT_COOLDOWN: f32 = 0.25;
+pub const ENEMY_SHOOT_COOLDOWN: f32 = 1.5;
+pub const ENEMY_SHOOT_RANGE: f32 = 360.0;
+pub const PLAYER_BULLET: ProjectileSpec = ProjectileSpec {
+    speed: 640.0,
+    lifetime: 0.8,
+    gravity_scale: 0.0,
+    damage: 1,
+    knockback: 200.0,
+    radius: 6.0,
+    color: Color::srgb(1.0, 0.85, 0.3),
+};
+pub const ENEMY_BULLET: ProjectileSpec = ProjectileSpec {
+    speed: 320.0,
+    lifetime: 2.0,
+    gravity_scale: 0.0,
+    damage: 1,
+    knockback: 300.0,
+    radius: 8.0,
+    color: Color::srgb(0.9, 0.3, 0.9),
+};
+
+/// Above this many coins, the HUD shows "x / y" instead of one slot per coin.
+pub const COIN_SLOT_LIMIT: usize = 6;
 pub const FERRIS_TEXTURE_SIZE: Vec2 = Vec2::new(460.0, 307.0);
 pub const COIN_HEIGHT: f32 = 44.0;
 pub const COIN_SIZE: Vec2 = Vec2::new(
@@ -21,6 +99,64 @@ pub const COIN_ICON_SIZE: Vec2 = Vec2::new(
     COIN_ICON_HEIGHT,
 );
 
+pub const GEM_SIZE: Vec2 = Vec2::splat(24.0);
+pub const HEALTH_PICKUP_SIZE: Vec2 = Vec2::splat(28.0);
+pub const KEY_SIZE: Vec2 = Vec2::new(32.0, 18.0);
+pub const POWER_UP_SIZE: Vec2 = Vec2::splat(30.0);
+
 pub const COIN_SLOT_EMPTY_COLOR: Color = Color::srgb(0.35, 0.35, 0.35);
 pub const COIN_SLOT_FILLED_COLOR: Color = Color::WHITE;
 pub const HUD_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+pub const IDLE_BOB_HEIGHT: f32 = 6.0;
+pub const IDLE_BOB_SPEED: f32 = 3.0;
+pub const IDLE_SPIN_SPEED: f32 = 2.5;
+pub const IDLE_SHIMMER: f32 = 0.35;
+/// What the shimmer glints towards. Warm, so it shows on white coins too.
+pub const IDLE_HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.85, 0.35);
+pub const COIN_FLIGHT_SECONDS: f32 = 0.6;
+pub const COIN_FLIGHT_Z: f32 = 5.0;
+
+pub const COIN_POINTS: u32 = 100;
+pub const GEM_POINTS: u32 = 500;
+pub const HEALTH_PICKUP_POINTS: u32 = 50;
+pub const KEY_POINTS: u32 = 200;
+pub const POWER_UP_POINTS: u32 = 150;
+pub const COMBO_WINDOW_SECONDS: f32 = 1.5;
+pub const COMBO_MAX_MULTIPLIER: u32 = 5;
+pub const TIME_BONUS_PER_SECOND: u32 = 50;
+pub const NO_DAMAGE_BONUS: u32 = 2000;
+pub const HIGH_SCORE_ENTRIES: usize = 5;
+
+pub const PLAYER_HIT_TRAUMA: f32 = 0.5;
+pub const PLAYER_HIT_STOP_SECONDS: f32 = 0.08;
+pub const PLAYER_HIT_KICK: f32 = 12.0;
+pub const ENEMY_DEFEAT_TRAUMA: f32 = 0.25;
+pub const ENEMY_DEFEAT_STOP_SECONDS: f32 = 0.05;
+pub const CRUSHER_SLAM_TRAUMA: f32 = 0.3;
+
+pub const PARTICLE_SIZE: f32 = 6.0;
+pub const PARTICLE_SPEED: f32 = 160.0;
+pub const PARTICLE_GRAVITY: f32 = 400.0;
+pub const PARTICLE_LIFETIME: f32 = 0.5;
+pub const COIN_PARTICLE_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+pub const TOAST_SECONDS: f32 = 2.5;
+pub const LAVA_COLOR: Color = Color::srgb(0.9, 0.3, 0.1);
+pub const SPIKES_COLOR: Color = Color::srgb(0.75, 0.75, 0.8);
+pub const CRUSHER_COLOR: Color = Color::srgb(0.15, 0.15, 0.18);
+pub const ENEMY_COLOR: Color = Color::srgb(0.7, 0.2, 0.25);
+pub const GEM_COLOR: Color = Color::srgb(0.3, 0.85, 0.95);
+pub const HEALTH_PICKUP_COLOR: Color = Color::srgb(0.95, 0.3, 0.35);
+pub const KEY_COLOR: Color = Color::srgb(0.95, 0.8, 0.2);
+pub const DOOR_COLOR: Color = Color::srgb(0.45, 0.3, 0.2);
+pub const LOCKED_DOOR_COLOR: Color = Color::srgb(0.7, 0.55, 0.15);
+pub const SWITCH_OFF_COLOR: Color = Color::srgb(0.5, 0.2, 0.2);
+pub const SWITCH_ON_COLOR: Color = Color::srgb(0.3, 0.8, 0.35);
+pub const GOAL_COLOR: Color = Color::srgb(0.95, 0.95, 0.4);
+pub const MECHANISM_COLOR: Color = Color::srgb(0.3, 0.35, 0.45);
+pub const POWER_UP_COLOR: Color = Color::srgb(0.75, 0.4, 0.95);
+pub const SPEED_BOOST_COLOR: Color = Color::srgb(0.95, 0.55, 0.2);
+pub const HIGH_JUMP_COLOR: Color = Color::srgb(0.4, 0.9, 0.4);
+pub const LOW_GRAVITY_COLOR: Color = Color::srgb(0.55, 0.75, 1.0);
+pub const MAGNET_COLOR: Color = Color::srgb(0.9, 0.3, 0.35);
+pub const SHIELD_COLOR: Color = Color::srgb(0.3, 0.85, 0.9);
@@ -0,0 +1,167 @@
+//! Walking enemies: patrols, waypoints and chasers that can be stomped.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    components::{Enemy, EnemyBehavior, Health, Player, Shooter, Staggered, Surroundings, Team},
+    events::{Damage, Died},
+    plugins::physics::GameLayer,
+    states::GameState,
+};
+
+use super::{
+    constants::{
+        ENEMY_BULLET, ENEMY_COLOR, ENEMY_CONTACT_DAMAGE, ENEMY_KNOCKBACK, ENEMY_SHOOT_COOLDOWN,
+        ENEMY_SHOOT_RANGE, ENEMY_SIZE, ENEMY_SPEED, PLAYER_SIZE, STOMP_BOUNCE_SPEED,
+        STOMP_TOLERANCE,
+    },
+    level::EnemySpec,
+};
+
+/// How close an enemy needs to get to a waypoint before heading to the next one.
+const WAYPOINT_REACHED: f32 = 4.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(despawn_defeated_enemies);
+}
+
+pub fn spawn_enemies(commands: &mut Commands, enemies: &[EnemySpec]) {
+    for spec in enemies {
+        let mut enemy = commands.spawn((
+            Name::new("Enemy"),
+            DespawnOnExit(GameState::Playing),
+            Enemy {
+                direction: 1.0,
+                speed: ENEMY_SPEED,
+            },
+            spec.behavior.clone(),
+            Health::new(1),
+            Surroundings::new(ENEMY_SIZE),
+            Sprite {
+                color: ENEMY_COLOR,
+                custom_size: Some(ENEMY_SIZE),
+                ..default()
+            },
+            Transform::from_translation(spec.position.extend(0.5)),
+            RigidBody::Dynamic,
+            Collider::rectangle(ENEMY_SIZE.x, ENEMY_SIZE.y),
+            LinearVelocity::ZERO,
+            Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+            Restitution::ZERO,
+            LockedAxes::ROTATION_LOCKED,
+            CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL),
+            CollidingEntities::default(),
+        ));
+        if spec.ranged {
+            enemy.insert(Shooter::new(
+                Team::Enemy,
+                ENEMY_BULLET,
+                ENEMY_SHOOT_COOLDOWN,
+                ENEMY_SHOOT_RANGE,
+            ));
+        }
+    }
+}
+
+pub fn drive_enemies(
+    mut enemies: Query<
+        (
+            &mut Enemy,
+            &mut EnemyBehavior,
+            &Transform,
+            &Surroundings,
+            &mut LinearVelocity,
+        ),
+        Without<Staggered>,
+    >,
+    players: Query<&Transform, With<Player>>,
+) {
+    for (mut enemy, mut behavior, transform, surroundings, mut velocity) in enemies.iter_mut() {
+        let position = transform.translation.xy();
+
+        let (direction, speed) = match &mut *behavior {
+            EnemyBehavior::Patrol => (patrol(&mut enemy, position.x, surroundings), enemy.speed),
+            EnemyBehavior::Waypoints { points, next } => {
+                if points.is_empty() {
+                    velocity.x = 0.0;
+                    continue;
+                }
+                if (points[*next].x - position.x).abs() <= WAYPOINT_REACHED {
+                    *next = (*next + 1) % points.len();
+                }
+                ((points[*next].x - position.x).signum(), enemy.speed)
+            }
+            EnemyBehavior::Chase { range, speed } => {
+                let target = players
+                    .iter()
+                    .map(|player| player.translation.xy())
+                    .filter(|player| player.distance(position) <= *range)
+                    .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+                match target {
+                    Some(target) => ((target.x - position.x).signum(), *speed),
+                    None => (patrol(&mut enemy, position.x, surroundings), enemy.speed),
+                }
+            }
+        };
+
+        enemy.direction = direction;
+        // Never walk off a ledge or into a wall; patrols have already turned around by now.
+        let blocked = surroundings.is_grounded()
+            && (surroundings.edge_ahead(position.x, direction)
+                || surroundings.wall_ahead(direction));
+        velocity.x = if blocked { 0.0 } else { direction * speed };
+    }
+}
+
+/// Turns around at walls and platform edges, returning the new heading.
+fn patrol(enemy: &mut Enemy, x: f32, surroundings: &Surroundings) -> f32 {
+    if surroundings.is_grounded()
+        && (surroundings.edge_ahead(x, enemy.direction) || surroundings.wall_ahead(enemy.direction))
+    {
+        enemy.direction = -enemy.direction;
+    }
+    enemy.direction
+}
+
+pub fn resolve_enemy_contacts(
+    mut commands: Commands,
+    mut players: Query<(Entity, &Transform, &mut LinearVelocity, &CollidingEntities), With<Player>>,
+    enemies: Query<(&Transform, &Health), With<Enemy>>,
+) {
+    for (player, player_transform, mut velocity, colliding) in players.iter_mut() {
+        let player_position = player_transform.translation.xy();
+        let player_bottom = player_position.y - PLAYER_SIZE.y * 0.5;
+
+        for &entity in colliding.iter() {
+            let Ok((enemy_transform, health)) = enemies.get(entity) else {
+                continue;
+            };
+            if health.is_dead() {
+                continue;
+            }
+            let enemy_position = enemy_transform.translation.xy();
+            let enemy_top = enemy_position.y + ENEMY_SIZE.y * 0.5;
+
+            if player_bottom >= enemy_top - STOMP_TOLERANCE {
+                commands.trigger(Damage::lethal(entity));
+                velocity.y = STOMP_BOUNCE_SPEED;
+            } else {
+                let away = (player_position.x - enemy_position.x).signum();
+                let knockback = Vec2::new(away * ENEMY_KNOCKBACK.x, ENEMY_KNOCKBACK.y);
+                commands
+                    .trigger(Damage::new(player, ENEMY_CONTACT_DAMAGE).with_knockback(knockback));
+            }
+        }
+    }
+}
+
+fn despawn_defeated_enemies(
+    died: On<Died>,
+    mut commands: Commands,
+    enemies: Query<(), With<Enemy>>,
+) {
+    if enemies.contains(died.entity) {
+        commands.entity(died.entity).despawn();
+    }
+}
@@ -0,0 +1,53 @@
+//! Camera feedback for impactful gameplay events.
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{Enemy, Invulnerable, Player, Shielded},
+    events::{CameraKick, CameraShake, Damage, Died, HitStop},
+};
+
+use super::constants::{
+    ENEMY_DEFEAT_STOP_SECONDS, ENEMY_DEFEAT_TRAUMA, PLAYER_HIT_KICK, PLAYER_HIT_STOP_SECONDS,
+    PLAYER_HIT_TRAUMA,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(shake_on_player_hit)
+        .add_observer(shake_on_enemy_defeat);
+}
+
+fn shake_on_player_hit(
+    damage: On<Damage>,
+    mut commands: Commands,
+    players: Query<(Has<Invulnerable>, Has<Shielded>), With<Player>>,
+) {
+    let Ok((invulnerable, shielded)) = players.get(damage.entity) else {
+        return;
+    };
+    // Mirror the damage pipeline so ignored hits don't shake.
+    if (invulnerable || shielded) && !damage.lethal {
+        return;
+    }
+    commands.trigger(CameraShake {
+        trauma: PLAYER_HIT_TRAUMA,
+    });
+    commands.trigger(HitStop {
+        secs: PLAYER_HIT_STOP_SECONDS,
+    });
+    commands.trigger(CameraKick {
+        offset: damage.knockback.normalize_or_zero() * PLAYER_HIT_KICK,
+    });
+}
+
+fn shake_on_enemy_defeat(died: On<Died>, mut commands: Commands, enemies: Query<(), With<Enemy>>) {
+    if !enemies.contains(died.entity) {
+        return;
+    }
+    commands.trigger(CameraShake {
+        trauma: ENEMY_DEFEAT_TRAUMA,
+    });
+    commands.trigger(HitStop {
+        secs: ENEMY_DEFEAT_STOP_SECONDS,
+    });
+}
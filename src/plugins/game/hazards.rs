@@ -0,0 +1,141 @@
+//! Lava, spikes and crushers. Contact is turned into [`Damage`](crate::events::Damage) so
+//! hazards never touch health themselves.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    components::{Crusher, CrusherPhase, Ground, Hazard, Health, Platform},
+    events::CameraShake,
+    states::GameState,
+};
+
+use super::{
+    constants::{
+        CRUSHER_COLOR, CRUSHER_HITBOX_HEIGHT, CRUSHER_SLAM_TRAUMA, LAVA_COLOR, SPIKES_COLOR,
+        SPIKES_DAMAGE, SPIKES_KNOCKBACK,
+    },
+    level::{HazardKind, HazardSpec},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (drive_crushers, damage_from_hazards)
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+pub fn spawn_hazards(commands: &mut Commands, hazards: &[HazardSpec]) {
+    for spec in hazards {
+        match spec.kind {
+            HazardKind::Lava => {
+                commands.spawn((
+                    Name::new("Lava"),
+                    DespawnOnExit(GameState::Playing),
+                    Hazard::lava(),
+                    hazard_sprite(LAVA_COLOR, spec.size),
+                    Transform::from_translation(spec.position.extend(0.0)),
+                    RigidBody::Static,
+                    Collider::rectangle(spec.size.x, spec.size.y),
+                    Sensor,
+                    CollidingEntities::default(),
+                ));
+            }
+            HazardKind::Spikes { facing } => {
+                // Sprites and colliders are authored pointing up, then rotated to face.
+                let rotation = Quat::from_rotation_z(facing.rotation_from_y().as_radians());
+                commands.spawn((
+                    Name::new("Spikes"),
+                    DespawnOnExit(GameState::Playing),
+                    Hazard::spikes(facing, SPIKES_DAMAGE, SPIKES_KNOCKBACK),
+                    hazard_sprite(SPIKES_COLOR, spec.size),
+                    Transform::from_translation(spec.position.extend(0.0)).with_rotation(rotation),
+                    RigidBody::Static,
+                    Collider::rectangle(spec.size.x, spec.size.y),
+                    Sensor,
+                    CollidingEntities::default(),
+                ));
+            }
+            HazardKind::Crusher { travel, schedule } => {
+                let hitbox = commands
+                    .spawn((
+                        Name::new("CrusherHitbox"),
+                        Hazard::crusher(),
+                        Transform::from_xyz(0.0, -(spec.size.y + CRUSHER_HITBOX_HEIGHT) * 0.5, 0.0),
+                        Collider::rectangle(spec.size.x - 4.0, CRUSHER_HITBOX_HEIGHT),
+                        Sensor,
+                        CollidingEntities::default(),
+                    ))
+                    .id();
+                commands
+                    .spawn((
+                        Name::new("Crusher"),
+                        DespawnOnExit(GameState::Playing),
+                        Crusher::new(spec.position, travel, schedule, hitbox),
+                        // Players can stand on a resting crusher.
+                        Ground,
+                        Platform { size: spec.size },
+                        hazard_sprite(CRUSHER_COLOR, spec.size),
+                        Transform::from_translation(spec.position.extend(0.0)),
+                        RigidBody::Kinematic,
+                        Collider::rectangle(spec.size.x, spec.size.y),
+                        LinearVelocity::ZERO,
+                    ))
+                    .add_child(hitbox);
+            }
+        }
+    }
+}
+
+fn hazard_sprite(color: Color, size: Vec2) -> Sprite {
+    Sprite {
+        color,
+        custom_size: Some(size),
+        ..default()
+    }
+}
+
+pub fn drive_crushers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut crushers: Query<(&mut Crusher, &mut Transform, &mut LinearVelocity)>,
+    mut hitboxes: Query<&mut Hazard>,
+) {
+    for (mut crusher, mut transform, mut velocity) in crushers.iter_mut() {
+        if crusher.timer.tick(time.delta()).is_finished() {
+            // Snap to the phase endpoint so velocity integration never drifts.
+            transform.translation = crusher.phase_end().extend(transform.translation.z);
+            crusher.advance();
+            if crusher.phase == CrusherPhase::Holding {
+                commands.trigger(CameraShake {
+                    trauma: CRUSHER_SLAM_TRAUMA,
+                });
+            }
+        }
+        velocity.0 = crusher.phase_velocity();
+
+        if let Ok(mut hitbox) = hitboxes.get_mut(crusher.hitbox) {
+            hitbox.armed = crusher.phase == CrusherPhase::Slamming;
+        }
+    }
+}
+
+pub fn damage_from_hazards(
+    mut commands: Commands,
+    hazards: Query<(&Hazard, &GlobalTransform, &CollidingEntities)>,
+    victims: Query<&GlobalTransform, With<Health>>,
+) {
+    for (hazard, hazard_transform, colliding) in hazards.iter() {
+        for &victim in colliding.iter() {
+            let Ok(victim_transform) = victims.get(victim) else {
+                continue;
+            };
+            let offset = victim_transform.translation().xy() - hazard_transform.translation().xy();
+            if hazard.hits(offset) {
+                commands.trigger(hazard.damage_to(victim));
+            }
+        }
+    }
+}
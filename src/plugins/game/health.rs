@@ -0,0 +1,120 @@
+//! Health and the shared damage pipeline.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    components::{Health, Invulnerable, Shielded, Staggered},
+    events::{Damage, Died},
+};
+
+use super::constants::{INVULNERABILITY_SECONDS, STAGGER_SECONDS};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(apply_damage)
+        .add_systems(FixedUpdate, (tick_invulnerability, tick_stagger));
+}
+
+fn apply_damage(
+    damage: On<Damage>,
+    mut commands: Commands,
+    mut targets: Query<(
+        &mut Health,
+        Has<Invulnerable>,
+        Has<Shielded>,
+        Option<&mut LinearVelocity>,
+    )>,
+) {
+    let Ok((mut health, invulnerable, shielded, velocity)) = targets.get_mut(damage.entity) else {
+        return;
+    };
+    if health.is_dead() || ((invulnerable || shielded) && !damage.lethal) {
+        return;
+    }
+
+    health.current = if damage.lethal {
+        0
+    } else {
+        health.current.saturating_sub(damage.amount)
+    };
+
+    if let Some(mut velocity) = velocity.filter(|_| damage.knockback != Vec2::ZERO) {
+        velocity.0 = damage.knockback;
+        commands.entity(damage.entity).insert(Staggered {
+            timer: Timer::from_seconds(STAGGER_SECONDS, TimerMode::Once),
+        });
+    }
+
+    if health.is_dead() {
+        commands.trigger(Died {
+            entity: damage.entity,
+        });
+    } else {
+        commands.entity(damage.entity).insert(Invulnerable {
+            timer: Timer::from_seconds(INVULNERABILITY_SECONDS, TimerMode::Once),
+        });
+    }
+}
+
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in query.iter_mut() {
+        if invulnerable.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+fn tick_stagger(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Staggered)>,
+) {
+    for (entity, mut staggered) in query.iter_mut() {
+        if staggered.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Staggered>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin));
+        app
+    }
+
+    #[test]
+    fn damage_reduces_health_and_grants_invulnerability() {
+        let mut app = setup();
+        let entity = app.world_mut().spawn(Health::new(3)).id();
+
+        app.world_mut().trigger(Damage::new(entity, 1));
+        app.world_mut().flush();
+        app.world_mut().trigger(Damage::new(entity, 1));
+        app.world_mut().flush();
+
+        // The second hit lands during invulnerability and is ignored.
+        assert_eq!(app.world().get::<Health>(entity).unwrap().current, 2);
+        assert!(app.world().get::<Invulnerable>(entity).is_some());
+    }
+
+    #[test]
+    fn lethal_damage_ignores_invulnerability() {
+        let mut app = setup();
+        let entity = app.world_mut().spawn(Health::new(3)).id();
+
+        app.world_mut().trigger(Damage::new(entity, 1));
+        app.world_mut().flush();
+        app.world_mut().trigger(Damage::lethal(entity));
+        app.world_mut().flush();
+
+        assert!(app.world().get::<Health>(entity).unwrap().is_dead());
+    }
+}
@@ -0,0 +1,327 @@
+//! Level layout data.
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        CollectibleKind, CrusherSchedule, EnemyBehavior, LogicId, PowerUpKind, SwitchKind,
+    },
+    plugins::camera::CameraBounds,
+};
+
+use super::constants::{
+    COIN_SIZE, CRUSHER_SIZE, DOOR_SIZE, ENEMY_CHASE_SPEED, ENEMY_SIZE, GOAL_SIZE, LAVA_SIZE,
+    LIFT_SPEED, PLATFORM_SIZE, PRESSURE_PLATE_SIZE, SMALL_PLATFORM_SIZE, SPIKES_SIZE, SWITCH_SIZE,
+};
+
+/// Everything needed to spawn a level. Spawn functions read from this instead of hardcoding
+/// positions, so new content only needs new data.
+#[derive(Resource, Clone, Debug)]
+pub struct LevelData {
+    pub id: String,
+    pub player_spawn: Vec2,
+    /// Where the level ends.
+    pub goal: Vec2,
+    /// Finishing faster than this earns a time bonus.
+    pub par_secs: f32,
+    /// Where the camera may look.
+    pub camera_bounds: CameraBounds,
+    pub platforms: Vec<PlatformSpec>,
+    pub collectibles: Vec<CollectibleSpec>,
+    pub hazards: Vec<HazardSpec>,
+    pub enemies: Vec<EnemySpec>,
+    pub doors: Vec<DoorSpec>,
+    pub switches: Vec<SwitchSpec>,
+    pub mechanisms: Vec<MechanismSpec>,
+    /// Background layers, back to front.
+    pub parallax: Vec<ParallaxSpec>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlatformSpec {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub color: Color,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CollectibleSpec {
+    pub kind: CollectibleKind,
+    pub value: u32,
+    pub position: Vec2,
+    /// The effect granted by a [`CollectibleKind::POWER_UP`].
+    pub power_up: Option<PowerUpKind>,
+    /// The lock a [`CollectibleKind::KEY`] opens.
+    pub key: Option<LogicId>,
+}
+
+impl CollectibleSpec {
+    pub const fn new(kind: CollectibleKind, value: u32, position: Vec2) -> Self {
+        Self {
+            kind,
+            value,
+            position,
+            power_up: None,
+            key: None,
+        }
+    }
+
+    pub const fn power_up(power_up: PowerUpKind, position: Vec2) -> Self {
+        Self {
+            kind: CollectibleKind::POWER_UP,
+            value: 1,
+            position,
+            power_up: Some(power_up),
+            key: None,
+        }
+    }
+
+    pub const fn key(key: LogicId, position: Vec2) -> Self {
+        Self {
+            kind: CollectibleKind::KEY,
+            value: 1,
+            position,
+            power_up: None,
+            key: Some(key),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct HazardSpec {
+    pub kind: HazardKind,
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnemySpec {
+    pub position: Vec2,
+    pub behavior: EnemyBehavior,
+    /// Ranged enemies shoot at players within range.
+    pub ranged: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DoorSpec {
+    pub id: LogicId,
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Key needed to open the door. Locked doors ignore switches.
+    pub lock: Option<LogicId>,
+}
+
+/// A switch and the IDs of the doors and mechanisms it drives.
+#[derive(Clone, Debug)]
+pub struct SwitchSpec {
+    pub kind: SwitchKind,
+    pub position: Vec2,
+    pub targets: Vec<LogicId>,
+}
+
+/// A platform that moves by `travel` while powered and returns when unpowered.
+#[derive(Clone, Copy, Debug)]
+pub struct MechanismSpec {
+    pub id: LogicId,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub travel: Vec2,
+    pub speed: f32,
+}
+
+/// A background image that scrolls past the camera by `scroll_factor`.
+#[derive(Clone, Copy, Debug)]
+pub struct ParallaxSpec {
+    pub image: &'static str,
+    /// Tint, mostly used to fade far layers into the clear color.
+    pub color: Color,
+    /// 0 stays fixed on screen, 1 stays fixed in the world. Farther layers take smaller values.
+    pub scroll_factor: Vec2,
+    pub tile_x: bool,
+    pub tile_y: bool,
+    /// World units per second, on top of the camera-driven scroll.
+    pub auto_scroll: Vec2,
+    pub offset: Vec2,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum HazardKind {
+    /// Instant-kill volume.
+    Lava,
+    /// Damages on contact from the `facing` side only.
+    Spikes { facing: Dir2 },
+    /// Moves by `travel` and back on `schedule`, killing anything under its leading face.
+    Crusher {
+        travel: Vec2,
+        schedule: CrusherSchedule,
+    },
+}
+
+const EXIT: LogicId = LogicId("exit");
+const EXIT_KEY: LogicId = LogicId("exit_key");
+const GATE: LogicId = LogicId("gate");
+const LIFT: LogicId = LogicId("lift");
+
+impl Default for LevelData {
+    fn default() -> Self {
+        let platform_top = -140.0 + PLATFORM_SIZE.y * 0.5;
+        let base_left = -PLATFORM_SIZE.x * 0.5 + COIN_SIZE.x * 0.5 + 8.0;
+        let crusher_rest = 150.0;
+        let enemy_height = ENEMY_SIZE.y * 0.5;
+        let small_platform_top = SMALL_PLATFORM_SIZE.y * 0.5;
+
+        Self {
+            id: "starter".into(),
+            player_spawn: Vec2::ZERO,
+            // On top of the lift once it has been raised.
+            goal: Vec2::new(
+                360.0,
+                60.0 + SMALL_PLATFORM_SIZE.y * 0.5 + GOAL_SIZE.y * 0.5,
+            ),
+            par_secs: 60.0,
+            camera_bounds: CameraBounds::Level(Rect::new(-700.0, -380.0, 700.0, 440.0)),
+            platforms: vec![
+                PlatformSpec {
+                    position: Vec2::new(0.0, -140.0),
+                    size: PLATFORM_SIZE,
+                    color: Color::srgb(0.2, 0.2, 0.25),
+                },
+                PlatformSpec {
+                    position: Vec2::new(140.0, -20.0),
+                    size: SMALL_PLATFORM_SIZE,
+                    color: Color::srgb(0.25, 0.25, 0.3),
+                },
+                PlatformSpec {
+                    position: Vec2::new(-160.0, 60.0),
+                    size: SMALL_PLATFORM_SIZE,
+                    color: Color::srgb(0.25, 0.25, 0.3),
+                },
+            ],
+            collectibles: vec![
+                CollectibleSpec::new(CollectibleKind::COIN, 1, Vec2::new(base_left, -100.0)),
+                CollectibleSpec::new(CollectibleKind::COIN, 1, Vec2::new(-160.0, 110.0)),
+                CollectibleSpec::new(CollectibleKind::COIN, 1, Vec2::new(140.0, 20.0)),
+                CollectibleSpec::new(CollectibleKind::GEM, 1, Vec2::new(20.0, 40.0)),
+                CollectibleSpec::new(CollectibleKind::HEALTH, 1, Vec2::new(-160.0, 180.0)),
+                CollectibleSpec::power_up(
+                    PowerUpKind::Speed,
+                    Vec2::new(-180.0, platform_top + 30.0),
+                ),
+                CollectibleSpec::power_up(
+                    PowerUpKind::HighJump,
+                    Vec2::new(100.0, platform_top + 30.0),
+                ),
+                CollectibleSpec::power_up(PowerUpKind::LowGravity, Vec2::new(200.0, 60.0)),
+                CollectibleSpec::power_up(PowerUpKind::Magnet, Vec2::new(60.0, 140.0)),
+                CollectibleSpec::power_up(PowerUpKind::Shield, Vec2::new(-220.0, 100.0)),
+                CollectibleSpec::key(EXIT_KEY, Vec2::new(-100.0, 100.0)),
+            ],
+            hazards: vec![
+                HazardSpec {
+                    kind: HazardKind::Lava,
+                    position: Vec2::new(0.0, -340.0),
+                    size: LAVA_SIZE,
+                },
+                HazardSpec {
+                    kind: HazardKind::Spikes { facing: Dir2::Y },
+                    position: Vec2::new(220.0, platform_top + SPIKES_SIZE.y * 0.5),
+                    size: SPIKES_SIZE,
+                },
+                HazardSpec {
+                    kind: HazardKind::Crusher {
+                        travel: Vec2::new(0.0, platform_top + CRUSHER_SIZE.y * 0.5 - crusher_rest),
+                        schedule: CrusherSchedule {
+                            wait: 1.5,
+                            slam: 0.25,
+                            hold: 0.5,
+                            rise: 1.2,
+                        },
+                    },
+                    position: Vec2::new(-90.0, crusher_rest),
+                    size: CRUSHER_SIZE,
+                },
+            ],
+            enemies: vec![
+                EnemySpec {
+                    position: Vec2::new(140.0, -20.0 + small_platform_top + enemy_height),
+                    behavior: EnemyBehavior::Patrol,
+                    ranged: true,
+                },
+                EnemySpec {
+                    position: Vec2::new(-160.0, 60.0 + small_platform_top + enemy_height),
+                    behavior: EnemyBehavior::Chase {
+                        range: 220.0,
+                        speed: ENEMY_CHASE_SPEED,
+                    },
+                    ranged: false,
+                },
+                EnemySpec {
+                    position: Vec2::new(60.0, platform_top + enemy_height),
+                    behavior: EnemyBehavior::Waypoints {
+                        points: vec![Vec2::new(-20.0, 0.0), Vec2::new(160.0, 0.0)],
+                        next: 0,
+                    },
+                    ranged: false,
+                },
+            ],
+            doors: vec![
+                DoorSpec {
+                    id: EXIT,
+                    position: Vec2::new(256.0, platform_top + DOOR_SIZE.y * 0.5),
+                    size: DOOR_SIZE,
+                    lock: Some(EXIT_KEY),
+                },
+                DoorSpec {
+                    id: GATE,
+                    position: Vec2::new(-248.0, platform_top + DOOR_SIZE.y * 0.5),
+                    size: DOOR_SIZE,
+                    lock: None,
+                },
+            ],
+            switches: vec![
+                SwitchSpec {
+                    kind: SwitchKind::Lever,
+                    position: Vec2::new(-20.0, platform_top + SWITCH_SIZE.y * 0.5),
+                    targets: vec![LIFT],
+                },
+                SwitchSpec {
+                    kind: SwitchKind::PressurePlate,
+                    position: Vec2::new(-200.0, platform_top + PRESSURE_PLATE_SIZE.y * 0.5),
+                    targets: vec![GATE],
+                },
+                SwitchSpec {
+                    kind: SwitchKind::Timed { seconds: 4.0 },
+                    position: Vec2::new(200.0, small_platform_top - 20.0 + SWITCH_SIZE.y * 0.5),
+                    targets: vec![GATE],
+                },
+            ],
+            mechanisms: vec![MechanismSpec {
+                id: LIFT,
+                position: Vec2::new(360.0, -140.0),
+                size: SMALL_PLATFORM_SIZE * Vec2::new(0.7, 1.0),
+                travel: Vec2::new(0.0, 200.0),
+                speed: LIFT_SPEED,
+            }],
+            parallax: vec![
+                ParallaxSpec {
+                    image: "textures/bevy.png",
+                    color: Color::srgba(0.55, 0.55, 0.6, 0.15),
+                    scroll_factor: Vec2::new(0.1, 0.05),
+                    tile_x: true,
+                    tile_y: true,
+                    auto_scroll: Vec2::new(-12.0, 0.0),
+                    offset: Vec2::ZERO,
+                },
+                ParallaxSpec {
+                    image: "textures/ferris.png",
+                    color: Color::srgba(0.3, 0.3, 0.35, 0.35),
+                    scroll_factor: Vec2::new(0.4, 0.2),
+                    tile_x: true,
+                    tile_y: false,
+                    auto_scroll: Vec2::ZERO,
+                    offset: Vec2::new(0.0, -200.0),
+                },
+            ],
+        }
+    }
+}
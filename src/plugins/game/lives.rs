@@ -0,0 +1,110 @@
+//! Run-level lives: respawning on death, game over when they run out, and retrying.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    components::{Health, Invulnerable, Player, Staggered},
+    events::Died,
+    plugins::menus::MenuAction,
+    resources::{CollectedCounts, Lives, RunStats},
+    states::GameState,
+};
+
+use super::{constants::PLAYER_LIVES, level::LevelData};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Lives::new(PLAYER_LIVES))
+        .init_resource::<RunStats>()
+        .add_observer(lose_life)
+        .add_observer(retry_run)
+        .add_systems(Update, track_run_time.run_if(in_state(GameState::Playing)));
+}
+
+fn lose_life(
+    died: On<Died>,
+    mut commands: Commands,
+    level: Res<LevelData>,
+    mut lives: ResMut<Lives>,
+    mut stats: ResMut<RunStats>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut players: Query<(&mut Transform, &mut LinearVelocity, &mut Health), With<Player>>,
+) {
+    let Ok((mut transform, mut velocity, mut health)) = players.get_mut(died.entity) else {
+        return;
+    };
+
+    stats.deaths += 1;
+    lives.remaining = lives.remaining.saturating_sub(1);
+    if lives.remaining == 0 {
+        next_state.set(GameState::GameOver);
+        return;
+    }
+
+    transform.translation = level.player_spawn.extend(transform.translation.z);
+    velocity.0 = Vec2::ZERO;
+    health.refill();
+    commands
+        .entity(died.entity)
+        .remove::<(Invulnerable, Staggered)>();
+}
+
+fn retry_run(
+    action: On<MenuAction>,
+    mut lives: ResMut<Lives>,
+    mut stats: ResMut<RunStats>,
+    mut counts: ResMut<CollectedCounts>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if *action != MenuAction::Retry {
+        return;
+    }
+    lives.reset();
+    *stats = RunStats::default();
+    *counts = CollectedCounts::default();
+    next_state.set(GameState::Playing);
+}
+
+fn track_run_time(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    stats.elapsed_secs += time.delta_secs();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, plugin))
+            .init_state::<GameState>()
+            .init_resource::<LevelData>();
+        app
+    }
+
+    #[test]
+    fn dying_spends_lives_until_game_over() {
+        let mut app = setup();
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Health::new(1),
+                Transform::default(),
+                LinearVelocity::ZERO,
+            ))
+            .id();
+
+        for _ in 0..PLAYER_LIVES {
+            app.world_mut().trigger(Died { entity: player });
+        }
+        app.update();
+
+        assert_eq!(app.world().resource::<Lives>().remaining, 0);
+        assert_eq!(app.world().resource::<RunStats>().deaths, PLAYER_LIVES);
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::GameOver
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! Level logic: keys, locked doors, switches and the mechanisms they drive. Switches reach
+//! their targets by [`LogicId`] through [`LogicSignal`], so wiring lives entirely in level data.
+
+use avian2d::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{
+        CollectibleKind, Door, Enemy, Ground, Interact, KeyId, KeyRing, LogicTarget, Mechanism,
+        Platform, Player, PlayerInput, Switch, SwitchKind,
+    },
+    events::{Collected, FocusCamera, LogicSignal},
+    resources::LogicInputs,
+    states::GameState,
+};
+
+use super::{
+    constants::{
+        DOOR_COLOR, DOOR_FOCUS_SECONDS, DOOR_OPEN_SPEED, LOCKED_DOOR_COLOR, MECHANISM_COLOR,
+        PRESSURE_PLATE_SIZE, SWITCH_OFF_COLOR, SWITCH_ON_COLOR, SWITCH_SIZE,
+    },
+    level::{DoorSpec, MechanismSpec, SwitchSpec},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LogicInputs>()
+        .add_observer(pick_up_keys)
+        .add_observer(route_signal)
+        .add_systems(OnExit(GameState::Playing), clear_logic_inputs)
+        .add_systems(
+            FixedUpdate,
+            (
+                (
+                    press_plates,
+                    pull_levers,
+                    touch_timed_switches,
+                    tick_timed_switches,
+                ),
+                unlock_doors,
+                animate_doors,
+                drive_mechanisms,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+}
+
+pub fn spawn_doors(commands: &mut Commands, doors: &[DoorSpec]) {
+    for spec in doors {
+        commands.spawn((
+            Name::new("Door"),
+            DespawnOnExit(GameState::Playing),
+            Door {
+                lock: spec.lock,
+                closed_position: spec.position,
+                open: false,
+                openness: 0.0,
+            },
+            LogicTarget(spec.id),
+            // Doors block like walls, so movers detect them the same way.
+            Ground,
+            Platform { size: spec.size },
+            Sprite {
+                color: if spec.lock.is_some() {
+                    LOCKED_DOOR_COLOR
+                } else {
+                    DOOR_COLOR
+                },
+                custom_size: Some(spec.size),
+                ..default()
+            },
+            Transform::from_translation(spec.position.extend(0.0)),
+            RigidBody::Static,
+            Collider::rectangle(spec.size.x, spec.size.y),
+            CollidingEntities::default(),
+        ));
+    }
+}
+
+pub fn spawn_switches(commands: &mut Commands, switches: &[SwitchSpec]) {
+    for spec in switches {
+        let size = match spec.kind {
+            SwitchKind::PressurePlate => PRESSURE_PLATE_SIZE,
+            SwitchKind::Lever | SwitchKind::Timed { .. } => SWITCH_SIZE,
+        };
+        commands.spawn((
+            Name::new("Switch"),
+            DespawnOnExit(GameState::Playing),
+            Switch::new(spec.kind, spec.targets.clone()),
+            Sprite {
+                color: SWITCH_OFF_COLOR,
+                custom_size: Some(size),
+                ..default()
+            },
+            Transform::from_translation(spec.position.extend(0.0)),
+            RigidBody::Static,
+            Collider::rectangle(size.x, size.y),
+            Sensor,
+            CollidingEntities::default(),
+        ));
+    }
+}
+
+pub fn spawn_mechanisms(commands: &mut Commands, mechanisms: &[MechanismSpec]) {
+    for spec in mechanisms {
+        commands.spawn((
+            Name::new("Mechanism"),
+            DespawnOnExit(GameState::Playing),
+            Mechanism {
+                origin: spec.position,
+                travel: spec.travel,
+                speed: spec.speed,
+                powered: false,
+            },
+            LogicTarget(spec.id),
+            Ground,
+            Platform { size: spec.size },
+            Sprite {
+                color: MECHANISM_COLOR,
+                custom_size: Some(spec.size),
+                ..default()
+            },
+            Transform::from_translation(spec.position.extend(0.0)),
+            RigidBody::Kinematic,
+            Collider::rectangle(spec.size.x, spec.size.y),
+        ));
+    }
+}
+
+fn pick_up_keys(
+    collected: On<Collected>,
+    keys: Query<&KeyId>,
+    mut collectors: Query<&mut KeyRing>,
+) {
+    if collected.kind != CollectibleKind::KEY {
+        return;
+    }
+    let Ok(key) = keys.get(collected.item) else {
+        return;
+    };
+    let Ok(mut ring) = collectors.get_mut(collected.collector) else {
+        return;
+    };
+    ring.0.insert(key.0);
+}
+
+/// Targets are active while any of their switches is, so one switch turning off doesn't undo
+/// another that is still on.
+fn route_signal(
+    signal: On<LogicSignal>,
+    mut commands: Commands,
+    mut inputs: ResMut<LogicInputs>,
+    mut doors: Query<(Entity, &LogicTarget, &mut Door)>,
+    mut mechanisms: Query<(&LogicTarget, &mut Mechanism)>,
+) {
+    let active = inputs.set(signal.target, signal.source, signal.active);
+    for (entity, target, mut door) in doors.iter_mut() {
+        if target.0 != signal.target || door.lock.is_some() || door.open == active {
+            continue;
+        }
+        door.open = active;
+        // The switch may be far from the door, so show what it did.
+        commands.trigger(FocusCamera {
+            entity,
+            secs: DOOR_FOCUS_SECONDS,
+        });
+    }
+    for (target, mut mechanism) in mechanisms.iter_mut() {
+        if target.0 == signal.target {
+            mechanism.powered = active;
+        }
+    }
+}
+
+fn clear_logic_inputs(mut inputs: ResMut<LogicInputs>) {
+    inputs.0.clear();
+}
+
+fn set_switch(
+    commands: &mut Commands,
+    entity: Entity,
+    switch: &mut Switch,
+    sprite: &mut Sprite,
+    active: bool,
+) {
+    if switch.active == active {
+        return;
+    }
+    switch.active = active;
+    sprite.color = if active {
+        SWITCH_ON_COLOR
+    } else {
+        SWITCH_OFF_COLOR
+    };
+    for &target in &switch.targets {
+        commands.trigger(LogicSignal {
+            source: entity,
+            target,
+            active,
+        });
+    }
+}
+
+fn press_plates(
+    mut commands: Commands,
+    mut switches: Query<(Entity, &mut Switch, &mut Sprite, &CollidingEntities)>,
+    weights: Query<(), Or<(With<Player>, With<Enemy>)>>,
+) {
+    for (entity, mut switch, mut sprite, colliding) in switches.iter_mut() {
+        if switch.kind != SwitchKind::PressurePlate {
+            continue;
+        }
+        let pressed = colliding.iter().any(|&other| weights.contains(other));
+        set_switch(&mut commands, entity, &mut switch, &mut sprite, pressed);
+    }
+}
+
+fn pull_levers(
+    mut commands: Commands,
+    mut held: Local<EntityHashSet>,
+    interact: Query<&ActionState, With<Action<Interact>>>,
+    players: Query<(Entity, &Actions<PlayerInput>), With<Player>>,
+    mut switches: Query<(Entity, &mut Switch, &mut Sprite, &CollidingEntities)>,
+) {
+    // Only the press itself pulls a lever, not holding the button down, and only for the
+    // player who pressed it.
+    let mut pulling = EntityHashSet::default();
+    for (player, actions) in players.iter() {
+        let pressed = interact
+            .iter_many(actions.iter())
+            .any(|state| *state == ActionState::Fired);
+        if !pressed {
+            held.remove(&player);
+        } else if held.insert(player) {
+            pulling.insert(player);
+        }
+    }
+    if pulling.is_empty() {
+        return;
+    }
+
+    for (entity, mut switch, mut sprite, colliding) in switches.iter_mut() {
+        if switch.kind != SwitchKind::Lever
+            || !colliding.iter().any(|other| pulling.contains(other))
+        {
+            continue;
+        }
+        let active = !switch.active;
+        set_switch(&mut commands, entity, &mut switch, &mut sprite, active);
+    }
+}
+
+fn touch_timed_switches(
+    mut commands: Commands,
+    players: Query<(), With<Player>>,
+    mut switches: Query<(Entity, &mut Switch, &mut Sprite, &CollidingEntities)>,
+) {
+    for (entity, mut switch, mut sprite, colliding) in switches.iter_mut() {
+        if !matches!(switch.kind, SwitchKind::Timed { .. })
+            || !colliding.iter().any(|&other| players.contains(other))
+        {
+            continue;
+        }
+        // Touching again restarts the countdown.
+        switch.timer.reset();
+        set_switch(&mut commands, entity, &mut switch, &mut sprite, true);
+    }
+}
+
+fn tick_timed_switches(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut switches: Query<(Entity, &mut Switch, &mut Sprite)>,
+) {
+    for (entity, mut switch, mut sprite) in switches.iter_mut() {
+        if !switch.active || !matches!(switch.kind, SwitchKind::Timed { .. }) {
+            continue;
+        }
+        if switch.timer.tick(time.delta()).is_finished() {
+            set_switch(&mut commands, entity, &mut switch, &mut sprite, false);
+        }
+    }
+}
+
+fn unlock_doors(
+    mut doors: Query<(&mut Door, &mut Sprite, &CollidingEntities)>,
+    players: Query<&KeyRing, With<Player>>,
+) {
+    for (mut door, mut sprite, colliding) in doors.iter_mut() {
+        let Some(lock) = door.lock else {
+            continue;
+        };
+        let has_key = colliding
+            .iter()
+            .filter_map(|&entity| players.get(entity).ok())
+            .any(|ring| ring.0.contains(&lock));
+        if has_key {
+            door.lock = None;
+            door.open = true;
+            sprite.color = DOOR_COLOR;
+        }
+    }
+}
+
+/// Slides doors up by their own height. An opening door stops colliding straight away; a
+/// closing one only blocks again once it is fully shut.
+fn animate_doors(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut doors: Query<(
+        Entity,
+        &mut Door,
+        &Platform,
+        &mut Transform,
+        Has<ColliderDisabled>,
+    )>,
+) {
+    let step = DOOR_OPEN_SPEED * time.delta_secs();
+    for (entity, mut door, platform, mut transform, disabled) in doors.iter_mut() {
+        let target = if door.open { 1.0 } else { 0.0 };
+        door.openness += (target - door.openness).clamp(-step, step);
+        transform.translation.y = door.closed_position.y + door.openness * platform.size.y;
+
+        if door.open && !disabled {
+            commands.entity(entity).insert(ColliderDisabled);
+        } else if !door.open && door.openness == 0.0 && disabled {
+            commands.entity(entity).remove::<ColliderDisabled>();
+        }
+    }
+}
+
+fn drive_mechanisms(
+    time: Res<Time>,
+    mut mechanisms: Query<(&Mechanism, &Transform, &mut LinearVelocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta == 0.0 {
+        return;
+    }
+    for (mechanism, transform, mut velocity) in mechanisms.iter_mut() {
+        let target = if mechanism.powered {
+            mechanism.origin + mechanism.travel
+        } else {
+            mechanism.origin
+        };
+        let offset = target - transform.translation.xy();
+        // Slow down on the last frame so the platform stops exactly on its end point.
+        let speed = mechanism.speed.min(offset.length() / delta);
+        velocity.0 = offset.normalize_or_zero() * speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::components::LogicId;
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin));
+        app
+    }
+
+    #[test]
+    fn signals_reach_only_matching_unlocked_doors() {
+        let mut app = setup();
+        let door = |lock| Door {
+            lock,
+            closed_position: Vec2::ZERO,
+            open: false,
+            openness: 0.0,
+        };
+        let gate = app
+            .world_mut()
+            .spawn((door(None), LogicTarget(LogicId("gate"))))
+            .id();
+        let locked = app
+            .world_mut()
+            .spawn((door(Some(LogicId("key"))), LogicTarget(LogicId("gate"))))
+            .id();
+        let other = app
+            .world_mut()
+            .spawn((door(None), LogicTarget(LogicId("other"))))
+            .id();
+
+        app.world_mut().trigger(LogicSignal {
+            source: Entity::PLACEHOLDER,
+            target: LogicId("gate"),
+            active: true,
+        });
+
+        assert!(app.world().get::<Door>(gate).unwrap().open);
+        assert!(!app.world().get::<Door>(locked).unwrap().open);
+        assert!(!app.world().get::<Door>(other).unwrap().open);
+    }
+
+    #[test]
+    fn a_door_stays_open_while_any_of_its_plates_is_pressed() {
+        let mut app = setup();
+        let gate = app
+            .world_mut()
+            .spawn((
+                Door {
+                    lock: None,
+                    closed_position: Vec2::ZERO,
+                    open: false,
+                    openness: 0.0,
+                },
+                LogicTarget(LogicId("gate")),
+            ))
+            .id();
+        let player = app.world_mut().spawn(Player).id();
+        let mut plate = || {
+            app.world_mut()
+                .spawn((
+                    Switch::new(SwitchKind::PressurePlate, vec![LogicId("gate")]),
+                    Sprite::default(),
+                    CollidingEntities::default(),
+                ))
+                .id()
+        };
+        let plates = [plate(), plate()];
+        let stand_on = |app: &mut App, plate: Entity, standing: bool| {
+            let mut colliding = app.world_mut().get_mut::<CollidingEntities>(plate).unwrap();
+            if standing {
+                colliding.0.insert(player);
+            } else {
+                colliding.0.remove(&player);
+            }
+            app.world_mut().run_system_once(press_plates).unwrap();
+            app.world().get::<Door>(gate).unwrap().open
+        };
+
+        assert!(stand_on(&mut app, plates[0], true));
+        assert!(stand_on(&mut app, plates[1], true));
+        // Stepping off one plate leaves the other holding the door open.
+        assert!(stand_on(&mut app, plates[0], false));
+        assert!(!stand_on(&mut app, plates[1], false));
+    }
+}
@@ -4,40 +4,158 @@ use bevy_enhanced_input::prelude::*;
 
 use crate::{
     components::PlayerInput,
-    plugins::fonts::FontAssets,
-    resources::{CoinState, FpsDisplay},
+    plugins::{
+        camera::{CameraConfiner, MainCamera},
+        fonts::FontAssets,
+    },
+    resources::{
+        CoinState, CollectibleKinds, FpsDisplay, InputReplay, JoinedPlayers, Lives, Score,
+    },
+    states::{GameState, InputMode, PauseState},
 };
 
+mod achievements;
+mod animation;
+mod collectibles;
 mod constants;
+mod enemies;
+mod feedback;
+mod hazards;
+mod health;
+mod level;
+mod lives;
+mod logic;
+mod parallax;
+mod particles;
+mod players;
+mod power_ups;
+mod projectiles;
+mod replay;
+mod score;
 mod spawn;
+mod surroundings;
 mod systems;
 
+pub use achievements::{Achievement, AchievementUnlocked, Achievements};
+use collectibles::spawn_collectible_counters;
+pub use collectibles::{
+    RegisterCollectibleExt, add_coins, announce_coin_collected, collect_collectibles,
+    count_spawned_coins, spawn_collectibles,
+};
 pub use constants::{COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, HUD_TEXT_COLOR};
+use enemies::{drive_enemies, resolve_enemy_contacts, spawn_enemies};
+use hazards::spawn_hazards;
+pub use level::{
+    CollectibleSpec, DoorSpec, EnemySpec, HazardKind, HazardSpec, LevelData, MechanismSpec,
+    ParallaxSpec, PlatformSpec, SwitchSpec,
+};
+use logic::{spawn_doors, spawn_mechanisms, spawn_switches};
+pub use parallax::ParallaxLayer;
+use parallax::spawn_parallax;
+pub use players::SPLIT_KEYBOARD_KEY;
+use players::spawn_joined_players;
+use power_ups::spawn_power_up_text;
+pub use replay::REPLAY_KEY;
+use score::{spawn_goal, spawn_score_text};
 use spawn::*;
+pub use surroundings::detect_surroundings;
+pub use systems::update_coin_counter;
 use systems::*;
-pub use systems::{collect_coins, update_coin_counter};
 
 pub(crate) fn plugin(app: &mut App) {
-    app.add_input_context::<PlayerInput>()
+    app.init_state::<GameState>()
+        .add_sub_state::<PauseState>()
+        .add_computed_state::<InputMode>()
+        // Evaluated once per fixed tick, so recorded and replayed input line up with the
+        // gameplay that reads it.
+        .add_input_context_to::<FixedPreUpdate, PlayerInput>()
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
         .init_resource::<CoinState>()
         .init_resource::<FpsDisplay>()
-        .add_systems(Startup, setup)
+        .init_resource::<LevelData>()
+        .add_plugins((
+            health::plugin,
+            hazards::plugin,
+            enemies::plugin,
+            projectiles::plugin,
+            lives::plugin,
+            collectibles::plugin,
+            particles::plugin,
+            achievements::plugin,
+            animation::plugin,
+            power_ups::plugin,
+            logic::plugin,
+            score::plugin,
+            feedback::plugin,
+            parallax::plugin,
+            players::plugin,
+            replay::plugin,
+        ))
+        .add_systems(OnEnter(GameState::Playing), setup)
+        .add_systems(
+            FixedUpdate,
+            (
+                detect_surroundings,
+                (apply_player_input, drive_enemies, resolve_enemy_contacts),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        )
         .add_systems(
             Update,
             (
-                apply_player_input,
-                collect_coins,
                 update_hud,
-                update_coin_counter,
-            ),
+                (rebuild_coin_counter, update_coin_counter).chain(),
+                update_lives_text,
+            )
+                .run_if(in_state(GameState::Playing)),
         );
 }
 
-fn setup(mut commands: Commands, assets: Res<AssetServer>, fonts: Res<FontAssets>) {
-    spawn_player(&mut commands, &assets);
-    spawn_platforms(&mut commands);
-    spawn_coins(&mut commands, &assets);
+fn setup(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    fonts: Res<FontAssets>,
+    level: Res<LevelData>,
+    lives: Res<Lives>,
+    kinds: Res<CollectibleKinds>,
+    joined: Res<JoinedPlayers>,
+    input_replay: Res<InputReplay>,
+    gamepads: Query<(), With<Gamepad>>,
+    mut coin_state: ResMut<CoinState>,
+    mut score: ResMut<Score>,
+    cameras: Query<Entity, With<MainCamera>>,
+) {
+    // Coins count themselves in as they spawn.
+    *coin_state = CoinState::default();
+    *score = Score::default();
+    for camera in cameras.iter() {
+        commands
+            .entity(camera)
+            .insert(CameraConfiner::new(level.camera_bounds.clone()));
+    }
+
+    spawn_parallax(&mut commands, &assets, &level.parallax);
+    // Others join from a gamepad or by splitting the keyboard, and stay for later levels.
+    spawn_joined_players(
+        &mut commands,
+        &assets,
+        level.player_spawn,
+        &joined,
+        &gamepads,
+        input_replay.is_replaying(),
+    );
+    spawn_platforms(&mut commands, &level.platforms);
+    spawn_collectibles(&mut commands, &assets, &kinds, &level.collectibles);
+    spawn_hazards(&mut commands, &level.hazards);
+    spawn_enemies(&mut commands, &level.enemies);
+    spawn_doors(&mut commands, &level.doors);
+    spawn_switches(&mut commands, &level.switches);
+    spawn_mechanisms(&mut commands, &level.mechanisms);
+    spawn_goal(&mut commands, level.goal);
     spawn_hud(&mut commands, &fonts);
-    spawn_coin_counter(&mut commands, &assets);
+    spawn_coin_counter(&mut commands, &fonts, &lives, &kinds);
+    spawn_collectible_counters(&mut commands, &fonts, &kinds);
+    spawn_power_up_text(&mut commands, &fonts);
+    spawn_score_text(&mut commands, &fonts);
 }
@@ -0,0 +1,175 @@
+//! Background layers that scroll slower (or faster) than the camera.
+
+use bevy::prelude::*;
+
+use crate::{
+    plugins::camera::{CameraMovement, MainCamera},
+    states::GameState,
+};
+
+use super::{
+    constants::{PARALLAX_Z_FAR, PARALLAX_Z_NEAR},
+    level::ParallaxSpec,
+};
+
+/// A background sprite placed relative to `MainCamera` every frame.
+#[derive(Component, Debug)]
+pub struct ParallaxLayer {
+    /// How fast the layer scrolls past compared to the world: 0 stays fixed on screen, 1 stays
+    /// fixed in the world.
+    pub scroll_factor: Vec2,
+    pub auto_scroll: Vec2,
+    pub tile_x: bool,
+    pub tile_y: bool,
+    pub offset: Vec2,
+    /// Distance covered by `auto_scroll` so far.
+    pub scrolled: Vec2,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        scroll_parallax
+            .after(CameraMovement)
+            .before(TransformSystems::Propagate)
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// Spawns the layers back to front, spreading them over the parallax z range.
+pub fn spawn_parallax(commands: &mut Commands, assets: &AssetServer, layers: &[ParallaxSpec]) {
+    let step = (PARALLAX_Z_NEAR - PARALLAX_Z_FAR) / layers.len().max(1) as f32;
+    for (index, spec) in layers.iter().enumerate() {
+        commands.spawn((
+            Name::new(format!("Parallax {index}")),
+            ParallaxLayer {
+                scroll_factor: spec.scroll_factor,
+                auto_scroll: spec.auto_scroll,
+                tile_x: spec.tile_x,
+                tile_y: spec.tile_y,
+                offset: spec.offset,
+                scrolled: Vec2::ZERO,
+            },
+            Sprite {
+                image: assets.load(spec.image),
+                color: spec.color,
+                image_mode: SpriteImageMode::Tiled {
+                    tile_x: spec.tile_x,
+                    tile_y: spec.tile_y,
+                    stretch_value: 1.0,
+                },
+                ..default()
+            },
+            Transform::from_translation(spec.offset.extend(PARALLAX_Z_FAR + step * index as f32)),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+/// Where a layer sits for a given camera position. Tiled axes wrap to within one tile of the
+/// camera, so a sprite one view plus two tiles wide always covers the screen.
+fn layer_position(layer: &ParallaxLayer, camera: Vec2, tile: Vec2) -> Vec2 {
+    let position = camera * (Vec2::ONE - layer.scroll_factor) + layer.offset + layer.scrolled;
+    let wrap = |tiled: bool, position: f32, camera: f32, tile: f32| {
+        if tiled && tile > 0.0 {
+            camera + (position - camera).rem_euclid(tile) - tile * 0.5
+        } else {
+            position
+        }
+    };
+    Vec2::new(
+        wrap(layer.tile_x, position.x, camera.x, tile.x),
+        wrap(layer.tile_y, position.y, camera.y, tile.y),
+    )
+}
+
+fn scroll_parallax(
+    time: Res<Time>,
+    images: Res<Assets<Image>>,
+    cameras: Query<(&Transform, &Projection), (With<MainCamera>, Without<ParallaxLayer>)>,
+    mut layers: Query<(&mut ParallaxLayer, &mut Sprite, &mut Transform)>,
+) {
+    let Ok((camera, projection)) = cameras.single() else {
+        return;
+    };
+    let view = match projection {
+        Projection::Orthographic(orthographic) => orthographic.area.size(),
+        _ => return,
+    };
+    let camera = camera.translation.truncate();
+
+    for (mut layer, mut sprite, mut transform) in layers.iter_mut() {
+        let Some(tile) = images.get(&sprite.image).map(Image::size_f32) else {
+            continue;
+        };
+        let scroll = layer.auto_scroll * time.delta_secs();
+        layer.scrolled += scroll;
+
+        let size = Vec2::new(
+            if layer.tile_x {
+                view.x + tile.x * 2.0
+            } else {
+                tile.x
+            },
+            if layer.tile_y {
+                view.y + tile.y * 2.0
+            } else {
+                tile.y
+            },
+        );
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+
+        let position = layer_position(&layer, camera, tile);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(scroll_factor: Vec2, tile_x: bool) -> ParallaxLayer {
+        ParallaxLayer {
+            scroll_factor,
+            auto_scroll: Vec2::ZERO,
+            tile_x,
+            tile_y: false,
+            offset: Vec2::ZERO,
+            scrolled: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn layers_scroll_past_by_their_scroll_factor() {
+        let far = layer(Vec2::splat(0.25), false);
+        let camera = Vec2::new(400.0, 100.0);
+        let position = layer_position(&far, camera, Vec2::splat(64.0));
+        // On screen, the layer moves a quarter as far as the world does.
+        assert_eq!(position - camera, Vec2::new(-100.0, -25.0));
+        let world = layer(Vec2::ONE, false);
+        assert_eq!(
+            layer_position(&world, camera, Vec2::splat(64.0)),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn tiled_layers_stay_within_a_tile_of_the_camera() {
+        let tiled = layer(Vec2::splat(0.25), true);
+        for camera_x in [-5000.0, -37.0, 0.0, 123.0, 9000.0] {
+            let camera = Vec2::new(camera_x, 0.0);
+            let position = layer_position(&tiled, camera, Vec2::splat(64.0));
+            assert!(
+                (position.x - camera_x).abs() <= 32.0,
+                "{camera_x}: {position}"
+            );
+            // Wrapping moves the layer by whole tiles only.
+            let unwrapped = camera_x * 0.75;
+            let tiles = (position.x - unwrapped) / 64.0;
+            assert!((tiles - tiles.round()).abs() < 1e-3, "{camera_x}: {tiles}");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Short-lived sprite bursts for pickups and impacts.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{events::CoinCollected, states::GameState};
+
+use super::constants::{
+    COIN_PARTICLE_COLOR, PARTICLE_GRAVITY, PARTICLE_LIFETIME, PARTICLE_SIZE, PARTICLE_SPEED,
+};
+
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(burst_on_coin).add_systems(
+        Update,
+        update_particles.run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// Spawns `count` particles flying outwards in an even ring.
+pub fn spawn_burst(commands: &mut Commands, position: Vec2, color: Color, count: usize) {
+    for index in 0..count {
+        let angle = TAU * index as f32 / count as f32;
+        commands.spawn((
+            Particle {
+                velocity: Vec2::from_angle(angle) * PARTICLE_SPEED,
+                lifetime: Timer::from_seconds(PARTICLE_LIFETIME, TimerMode::Once),
+            },
+            Sprite {
+                color,
+                custom_size: Some(Vec2::splat(PARTICLE_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(3.0)),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+fn burst_on_coin(collected: On<CoinCollected>, mut commands: Commands) {
+    spawn_burst(&mut commands, collected.position, COIN_PARTICLE_COLOR, 10);
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+        if particle.lifetime.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        particle.velocity.y -= PARTICLE_GRAVITY * delta;
+        transform.translation += (particle.velocity * delta).extend(0.0);
+        sprite
+            .color
+            .set_alpha(particle.lifetime.fraction_remaining());
+    }
+}
@@ -0,0 +1,439 @@
+//! Local multiplayer. The first player starts on the whole keyboard; pressing a button on a
+//! gamepad nobody is using hands it to that player, or to a new one once they have a pad.
+//! Pressing [`SPLIT_KEYBOARD_KEY`] splits the keyboard between the first player and a new one.
+//! When a player's gamepad disconnects the game pauses, and the next pad to connect is theirs.
+//! Everyone who joined is remembered in [`JoinedPlayers`] and respawned with each level.
+
+use bevy::{input::gamepad::GamepadConnectionEvent, prelude::*};
+
+use crate::{
+    components::{AwaitingGamepad, InputDevices, Player, PlayerNumber},
+    plugins::camera::CameraFrame,
+    resources::{JoinedPlayers, KeyboardHalf},
+    states::{GameState, InputMode, PauseState},
+};
+
+use super::{
+    constants::{MAX_PLAYERS, PLAYER_SIZE},
+    level::LevelData,
+    replay::replaying,
+    spawn::spawn_player,
+};
+
+/// On the right half, so the player it hands that half to is the one pressing it.
+pub const SPLIT_KEYBOARD_KEY: KeyCode = KeyCode::Enter;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<JoinedPlayers>().add_systems(
+        Update,
+        (
+            handle_gamepad_connections,
+            // Replays bring in their own players.
+            join_players.run_if(in_state(InputMode::Gameplay).and(not(replaying))),
+            remember_players.run_if(not(replaying)),
+            frame_players,
+        )
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// Spawns everyone who has joined at `position`, or only the first player for a replay, which
+/// brings in the rest itself. A gamepad that went away in the meantime is dropped, and a
+/// player left without any device waits for one.
+pub(super) fn spawn_joined_players(
+    commands: &mut Commands,
+    assets: &AssetServer,
+    position: Vec2,
+    joined: &JoinedPlayers,
+    gamepads: &Query<(), With<Gamepad>>,
+    replaying: bool,
+) {
+    let count = if replaying { 1 } else { joined.0.len() };
+    for &(number, mut devices) in joined.0.iter().take(count) {
+        if devices
+            .gamepad
+            .is_some_and(|gamepad| !gamepads.contains(gamepad))
+        {
+            devices.gamepad = None;
+        }
+        let player = spawn_player(commands, assets, position, number, devices);
+        if devices.keyboard.is_none() && devices.gamepad.is_none() {
+            commands.entity(player).insert(AwaitingGamepad);
+        }
+    }
+}
+
+/// The lowest player number not taken yet.
+fn free_number(taken: impl Iterator<Item = PlayerNumber>) -> Option<PlayerNumber> {
+    let taken: Vec<_> = taken.collect();
+    (0..MAX_PLAYERS)
+        .map(PlayerNumber)
+        .find(|number| !taken.contains(number))
+}
+
+/// New players appear on the first one, so they start on screen.
+pub(super) fn join_position<'a>(
+    players: impl Iterator<Item = (PlayerNumber, &'a Transform)>,
+    level: &LevelData,
+) -> Vec2 {
+    players
+        .min_by_key(|(number, _)| *number)
+        .map_or(level.player_spawn, |(_, transform)| {
+            transform.translation.xy()
+        })
+}
+
+/// Takes a disconnected gamepad away from its player and pauses until they have one again. A
+/// pad that connects goes to the first player left waiting.
+fn handle_gamepad_connections(
+    mut commands: Commands,
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut players: Query<
+        (
+            Entity,
+            &PlayerNumber,
+            &mut InputDevices,
+            Has<AwaitingGamepad>,
+        ),
+        With<Player>,
+    >,
+    mut next_pause: Option<ResMut<NextState<PauseState>>>,
+) {
+    for connection in connections.read() {
+        let gamepad = connection.gamepad;
+        let owner = players
+            .iter_mut()
+            .find(|(_, _, devices, _)| devices.gamepad == Some(gamepad));
+        if connection.disconnected() {
+            let Some((player, _, mut devices, _)) = owner else {
+                continue;
+            };
+            devices.gamepad = None;
+            commands.entity(player).insert(AwaitingGamepad);
+            if let Some(next_pause) = next_pause.as_mut() {
+                next_pause.set(PauseState::Paused);
+            }
+        } else if connection.connected() && owner.is_none() {
+            let next = players
+                .iter_mut()
+                .filter(|(.., waiting)| *waiting)
+                .min_by_key(|(_, number, ..)| **number);
+            if let Some((player, _, mut devices, _)) = next {
+                devices.gamepad = Some(gamepad);
+                commands.entity(player).remove::<AwaitingGamepad>();
+            }
+        }
+    }
+}
+
+fn join_players(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    level: Res<LevelData>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut players: Query<
+        (
+            Entity,
+            &PlayerNumber,
+            &mut InputDevices,
+            &Transform,
+            Has<AwaitingGamepad>,
+        ),
+        With<Player>,
+    >,
+) {
+    let position = join_position(
+        players
+            .iter()
+            .map(|(_, number, _, transform, _)| (*number, transform)),
+        &level,
+    );
+    let number = free_number(players.iter().map(|(_, number, ..)| *number));
+
+    let unassigned = gamepads.iter().find(|(entity, gamepad)| {
+        gamepad.get_just_pressed().next().is_some()
+            && !players
+                .iter()
+                .any(|(_, _, devices, ..)| devices.gamepad == Some(*entity))
+    });
+    if let Some((gamepad, _)) = unassigned {
+        // A player who lost their pad takes it first. Otherwise a lone keyboard player does,
+        // so one person can play on either.
+        let waiting = players
+            .iter_mut()
+            .filter(|(.., waiting)| *waiting)
+            .min_by_key(|(_, number, ..)| **number);
+        if let Some((player, _, mut devices, ..)) = waiting {
+            devices.gamepad = Some(gamepad);
+            commands.entity(player).remove::<AwaitingGamepad>();
+            return;
+        }
+        let lone = players.iter_mut().find(|(_, _, devices, ..)| {
+            devices.gamepad.is_none() && devices.keyboard == Some(KeyboardHalf::Whole)
+        });
+        if let Some((_, _, mut devices, ..)) = lone {
+            devices.gamepad = Some(gamepad);
+        } else if let Some(number) = number {
+            let devices = InputDevices {
+                keyboard: None,
+                gamepad: Some(gamepad),
+            };
+            spawn_player(&mut commands, &assets, position, number, devices);
+        }
+        return;
+    }
+
+    if !keys.just_pressed(SPLIT_KEYBOARD_KEY) {
+        return;
+    }
+    let Some(number) = number else {
+        return;
+    };
+    let whole = players
+        .iter_mut()
+        .find(|(_, _, devices, ..)| devices.keyboard == Some(KeyboardHalf::Whole));
+    if let Some((_, _, mut devices, ..)) = whole {
+        devices.keyboard = Some(KeyboardHalf::Left);
+        let devices = InputDevices {
+            keyboard: Some(KeyboardHalf::Right),
+            gamepad: None,
+        };
+        spawn_player(&mut commands, &assets, position, number, devices);
+    }
+}
+
+/// Keeps [`JoinedPlayers`] up to date as players join and swap devices.
+fn remember_players(
+    mut joined: ResMut<JoinedPlayers>,
+    players: Query<(&PlayerNumber, Ref<InputDevices>), With<Player>>,
+) {
+    if !players.iter().any(|(_, devices)| devices.is_changed()) {
+        return;
+    }
+    let mut players: Vec<_> = players
+        .iter()
+        .map(|(number, devices)| (*number, *devices))
+        .collect();
+    players.sort_by_key(|(number, _)| *number);
+    joined.set_if_neq(JoinedPlayers(players));
+}
+
+/// Frames every player once there is more than one, and leaves a lone player to the follow
+/// camera.
+fn frame_players(mut commands: Commands, players: Query<(Entity, Has<CameraFrame>), With<Player>>) {
+    let framed = players.iter().len() > 1;
+    for (player, has_frame) in players.iter() {
+        if framed && !has_frame {
+            commands.entity(player).insert(CameraFrame {
+                radius: PLAYER_SIZE.max_element(),
+            });
+        } else if !framed && has_frame {
+            commands.entity(player).remove::<CameraFrame>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{
+        ecs::system::RunSystemOnce, input::gamepad::GamepadConnection, state::app::StatesPlugin,
+    };
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin))
+            .init_asset::<Image>()
+            .init_state::<GameState>()
+            .add_sub_state::<PauseState>()
+            .add_message::<GamepadConnectionEvent>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LevelData>()
+            .init_resource::<JoinedPlayers>()
+            .add_systems(
+                Update,
+                (handle_gamepad_connections, join_players, remember_players).chain(),
+            );
+        let assets = app.world().resource::<AssetServer>().clone();
+        let mut commands = app.world_mut().commands();
+        spawn_player(
+            &mut commands,
+            &assets,
+            Vec2::ZERO,
+            PlayerNumber(0),
+            InputDevices {
+                keyboard: Some(KeyboardHalf::Whole),
+                gamepad: None,
+            },
+        );
+        app.world_mut().flush();
+        app
+    }
+
+    fn press(app: &mut App, gamepad: Entity) {
+        let mut gamepad = app.world_mut().get_mut::<Gamepad>(gamepad).unwrap();
+        gamepad.digital_mut().press(GamepadButton::South);
+    }
+
+    fn players(app: &mut App) -> Vec<(PlayerNumber, InputDevices)> {
+        let mut players = app
+            .world_mut()
+            .query::<(&PlayerNumber, &InputDevices)>()
+            .iter(app.world())
+            .map(|(number, devices)| (*number, *devices))
+            .collect::<Vec<_>>();
+        players.sort_by_key(|(number, _)| *number);
+        players
+    }
+
+    #[test]
+    fn the_first_pad_goes_to_the_keyboard_player_and_the_next_joins() {
+        let mut app = setup();
+        let first = app.world_mut().spawn(Gamepad::default()).id();
+        let second = app.world_mut().spawn(Gamepad::default()).id();
+
+        press(&mut app, first);
+        app.update();
+        press(&mut app, second);
+        app.update();
+
+        let players = players(&mut app);
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].1.gamepad, Some(first));
+        assert_eq!(players[0].1.keyboard, Some(KeyboardHalf::Whole));
+        assert_eq!(
+            players[1],
+            (
+                PlayerNumber(1),
+                InputDevices {
+                    keyboard: None,
+                    gamepad: Some(second),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn splitting_the_keyboard_adds_a_player_on_the_right_half() {
+        let mut app = setup();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(SPLIT_KEYBOARD_KEY);
+        app.update();
+
+        let players = players(&mut app);
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].1.keyboard, Some(KeyboardHalf::Left));
+        assert_eq!(players[1].1.keyboard, Some(KeyboardHalf::Right));
+    }
+
+    #[test]
+    fn a_lost_gamepad_pauses_until_another_connects() {
+        let mut app = setup();
+        let lost = app.world_mut().spawn(Gamepad::default()).id();
+        let assets = app.world().resource::<AssetServer>().clone();
+        let mut commands = app.world_mut().commands();
+        spawn_player(
+            &mut commands,
+            &assets,
+            Vec2::ZERO,
+            PlayerNumber(1),
+            InputDevices {
+                keyboard: None,
+                gamepad: Some(lost),
+            },
+        );
+        app.world_mut().flush();
+
+        app.world_mut().write_message(GamepadConnectionEvent::new(
+            lost,
+            GamepadConnection::Disconnected,
+        ));
+        app.update();
+        app.update();
+
+        assert_eq!(players(&mut app)[1].1.gamepad, None);
+        assert_eq!(
+            *app.world().resource::<State<PauseState>>().get(),
+            PauseState::Paused
+        );
+
+        let replacement = app.world_mut().spawn(Gamepad::default()).id();
+        app.world_mut().write_message(GamepadConnectionEvent::new(
+            replacement,
+            GamepadConnection::Connected {
+                name: "Pad".into(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+        app.update();
+
+        let players = players(&mut app);
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[1].1.gamepad, Some(replacement));
+        assert_eq!(players[0].1.gamepad, None);
+        assert!(
+            app.world_mut()
+                .query_filtered::<(), With<AwaitingGamepad>>()
+                .iter(app.world())
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn joined_players_are_respawned_without_gamepads_that_went_away() {
+        let mut app = setup();
+        let first = app.world_mut().spawn(Gamepad::default()).id();
+        let second = app.world_mut().spawn(Gamepad::default()).id();
+        press(&mut app, first);
+        app.update();
+        press(&mut app, second);
+        app.update();
+        let joined = app.world().resource::<JoinedPlayers>().clone();
+        assert_eq!(joined.0, players(&mut app));
+
+        // Retrying despawns everyone, and the second pad is unplugged on the game-over screen.
+        let entities: Vec<_> = app
+            .world_mut()
+            .query_filtered::<Entity, With<Player>>()
+            .iter(app.world())
+            .collect();
+        for entity in entities.into_iter().chain([second]) {
+            app.world_mut().despawn(entity);
+        }
+        app.world_mut()
+            .run_system_once(
+                move |mut commands: Commands,
+                      assets: Res<AssetServer>,
+                      gamepads: Query<(), With<Gamepad>>| {
+                    spawn_joined_players(
+                        &mut commands,
+                        &assets,
+                        Vec2::ZERO,
+                        &joined,
+                        &gamepads,
+                        false,
+                    );
+                },
+            )
+            .unwrap();
+
+        let players = players(&mut app);
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].1.gamepad, Some(first));
+        assert_eq!(players[0].1.keyboard, Some(KeyboardHalf::Whole));
+        assert_eq!(players[1].1, InputDevices::default());
+        let waiting = app
+            .world_mut()
+            .query_filtered::<&PlayerNumber, With<AwaitingGamepad>>()
+            .iter(app.world())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(waiting, [PlayerNumber(1)]);
+    }
+}
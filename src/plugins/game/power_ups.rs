@@ -0,0 +1,334 @@
+//! Timed power-ups. Movement effects go through the player's [`Movement`] modifier stack, so
+//! expiring a power-up only means dropping its modifier.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    components::{
+        ActivePowerUps, Collectible, CollectibleKind, Invulnerable, ModifierSource, Movement,
+        MovementModifier, PickedUp, Player, PlayerNumber, PowerUp, PowerUpKind, Shielded,
+        StackPolicy,
+    },
+    events::{Collected, Damage, Died},
+    plugins::fonts::FontAssets,
+    states::GameState,
+    utils::format_power_ups,
+};
+
+use super::constants::{
+    HIGH_JUMP_COLOR, HIGH_JUMP_MULTIPLIER, HIGH_JUMP_SECONDS, HUD_TEXT_COLOR,
+    INVULNERABILITY_SECONDS, LOW_GRAVITY_COLOR, LOW_GRAVITY_SCALE, LOW_GRAVITY_SECONDS,
+    MAGNET_COLOR, MAGNET_PULL_SPEED, MAGNET_RADIUS, MAGNET_SECONDS, SHIELD_COLOR, SHIELD_SECONDS,
+    SPEED_BOOST_COLOR, SPEED_BOOST_MAX_STACKS, SPEED_BOOST_PER_STACK, SPEED_BOOST_SECONDS,
+};
+
+#[derive(Component)]
+pub struct PowerUpText;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(grant_power_up)
+        .add_observer(absorb_with_shield)
+        .add_observer(clear_power_ups_on_death)
+        .add_systems(
+            FixedUpdate,
+            (
+                (tick_power_ups, apply_power_up_effects).chain(),
+                pull_coins_with_magnet,
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            update_power_up_text.run_if(in_state(GameState::Playing)),
+        );
+}
+
+/// Duration and stacking policy for each kind.
+pub const fn power_up_rules(kind: PowerUpKind) -> (f32, StackPolicy) {
+    match kind {
+        PowerUpKind::Speed => (
+            SPEED_BOOST_SECONDS,
+            StackPolicy::Stack {
+                max: SPEED_BOOST_MAX_STACKS,
+            },
+        ),
+        PowerUpKind::HighJump => (HIGH_JUMP_SECONDS, StackPolicy::Refresh),
+        PowerUpKind::LowGravity => (LOW_GRAVITY_SECONDS, StackPolicy::Refresh),
+        PowerUpKind::Magnet => (MAGNET_SECONDS, StackPolicy::Extend),
+        PowerUpKind::Shield => (SHIELD_SECONDS, StackPolicy::Refresh),
+    }
+}
+
+pub const fn power_up_color(kind: PowerUpKind) -> Color {
+    match kind {
+        PowerUpKind::Speed => SPEED_BOOST_COLOR,
+        PowerUpKind::HighJump => HIGH_JUMP_COLOR,
+        PowerUpKind::LowGravity => LOW_GRAVITY_COLOR,
+        PowerUpKind::Magnet => MAGNET_COLOR,
+        PowerUpKind::Shield => SHIELD_COLOR,
+    }
+}
+
+fn movement_modifier(kind: PowerUpKind, stacks: u32) -> Option<MovementModifier> {
+    let unchanged = MovementModifier {
+        source: ModifierSource::PowerUp(kind),
+        speed: 1.0,
+        jump_speed: 1.0,
+        gravity_scale: 1.0,
+    };
+    match kind {
+        PowerUpKind::Speed => Some(MovementModifier {
+            speed: 1.0 + SPEED_BOOST_PER_STACK * stacks as f32,
+            ..unchanged
+        }),
+        PowerUpKind::HighJump => Some(MovementModifier {
+            jump_speed: HIGH_JUMP_MULTIPLIER,
+            ..unchanged
+        }),
+        PowerUpKind::LowGravity => Some(MovementModifier {
+            gravity_scale: LOW_GRAVITY_SCALE,
+            ..unchanged
+        }),
+        PowerUpKind::Magnet | PowerUpKind::Shield => None,
+    }
+}
+
+pub fn spawn_power_up_text(commands: &mut Commands, fonts: &FontAssets) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Text::new(""),
+        TextFont {
+            font: fonts.default.clone(),
+            font_size: 32.0,
+            font_smoothing: FontSmoothing::None,
+            ..default()
+        },
+        TextColor(HUD_TEXT_COLOR),
+        PowerUpText,
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+fn grant_power_up(
+    collected: On<Collected>,
+    power_ups: Query<&PowerUp>,
+    mut collectors: Query<&mut ActivePowerUps>,
+) {
+    if collected.kind != CollectibleKind::POWER_UP {
+        return;
+    }
+    let Ok(power_up) = power_ups.get(collected.item) else {
+        return;
+    };
+    let Ok(mut active) = collectors.get_mut(collected.collector) else {
+        return;
+    };
+    let (duration, policy) = power_up_rules(power_up.0);
+    active.grant(power_up.0, duration, policy);
+}
+
+/// Spends the shield on the first non-lethal hit it blocks. [`Shielded`] is read by the damage
+/// pipeline before this removal is applied, so the hit itself is still absorbed.
+fn absorb_with_shield(
+    damage: On<Damage>,
+    mut commands: Commands,
+    mut targets: Query<&mut ActivePowerUps, (With<Shielded>, Without<Invulnerable>)>,
+) {
+    if damage.lethal {
+        return;
+    }
+    let Ok(mut active) = targets.get_mut(damage.entity) else {
+        return;
+    };
+    active.remove(PowerUpKind::Shield);
+    commands.entity(damage.entity).insert(Invulnerable {
+        timer: Timer::from_seconds(INVULNERABILITY_SECONDS, TimerMode::Once),
+    });
+}
+
+fn clear_power_ups_on_death(died: On<Died>, mut targets: Query<&mut ActivePowerUps>) {
+    let Ok(mut active) = targets.get_mut(died.entity) else {
+        return;
+    };
+    active.0.clear();
+}
+
+fn tick_power_ups(time: Res<Time>, mut query: Query<&mut ActivePowerUps>) {
+    for mut active in query.iter_mut() {
+        // Ticking alone shouldn't count as a change, only an expiry should.
+        let timers = active.bypass_change_detection();
+        for power_up in timers.0.iter_mut() {
+            power_up.timer.tick(time.delta());
+        }
+        if timers.0.iter().any(|power_up| power_up.timer.is_finished()) {
+            active.0.retain(|power_up| !power_up.timer.is_finished());
+        }
+    }
+}
+
+/// Rebuilds the power-up part of the modifier stack whenever the active set changes.
+fn apply_power_up_effects(
+    mut commands: Commands,
+    mut query: Query<(Entity, &ActivePowerUps, &mut Movement), Changed<ActivePowerUps>>,
+) {
+    for (entity, active, mut movement) in query.iter_mut() {
+        // Modifiers from anything else stay put.
+        movement
+            .modifiers
+            .retain(|modifier| !modifier.source.is_power_up());
+        movement.modifiers.extend(
+            active
+                .0
+                .iter()
+                .filter_map(|power_up| movement_modifier(power_up.kind, power_up.stacks)),
+        );
+        if active.has(PowerUpKind::Shield) {
+            commands.entity(entity).insert(Shielded);
+        } else {
+            commands.entity(entity).remove::<Shielded>();
+        }
+    }
+}
+
+fn pull_coins_with_magnet(
+    time: Res<Time>,
+    players: Query<(&ActivePowerUps, &Transform), With<Player>>,
+    mut coins: Query<(&Collectible, &mut Transform), (Without<Player>, Without<PickedUp>)>,
+) {
+    let step = MAGNET_PULL_SPEED * time.delta_secs();
+    for (active, player_transform) in players.iter() {
+        if !active.has(PowerUpKind::Magnet) {
+            continue;
+        }
+        let player = player_transform.translation.xy();
+        for (collectible, mut transform) in coins.iter_mut() {
+            let position = transform.translation.xy();
+            if collectible.kind != CollectibleKind::COIN
+                || position.distance(player) > MAGNET_RADIUS
+            {
+                continue;
+            }
+            // The idle bob is on the sprite, so it carries on around the new position.
+            let position = position.move_towards(player, step);
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+    }
+}
+
+/// Each player's power-ups, labelled by player once there is more than one.
+fn update_power_up_text(
+    players: Query<(&PlayerNumber, &ActivePowerUps), With<Player>>,
+    mut text: Query<&mut Text, With<PowerUpText>>,
+) {
+    let Ok(mut text) = text.single_mut() else {
+        return;
+    };
+    let mut players: Vec<_> = players.iter().collect();
+    players.sort_by_key(|(number, _)| **number);
+    let labelled = players.len() > 1;
+    let lines: Vec<_> = players
+        .into_iter()
+        .flat_map(|(number, active)| {
+            format_power_ups(active)
+                .lines()
+                .map(|line| {
+                    if labelled {
+                        format!("{} {line}", number.label())
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    text.0 = lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::components::MovementStats;
+
+    #[test]
+    fn speed_stacks_up_to_its_limit() {
+        let mut active = ActivePowerUps::default();
+        let (duration, policy) = power_up_rules(PowerUpKind::Speed);
+        for _ in 0..5 {
+            active.grant(PowerUpKind::Speed, duration, policy);
+        }
+
+        assert_eq!(
+            active.get(PowerUpKind::Speed).unwrap().stacks,
+            SPEED_BOOST_MAX_STACKS
+        );
+    }
+
+    #[test]
+    fn magnet_extends_remaining_time() {
+        let mut active = ActivePowerUps::default();
+        let (duration, policy) = power_up_rules(PowerUpKind::Magnet);
+        active.grant(PowerUpKind::Magnet, duration, policy);
+        active.grant(PowerUpKind::Magnet, duration, policy);
+
+        let timer = &active.get(PowerUpKind::Magnet).unwrap().timer;
+        assert_eq!(timer.remaining_secs(), duration * 2.0);
+    }
+
+    #[test]
+    fn modifiers_scale_base_movement() {
+        let mut movement = Movement::new(MovementStats {
+            speed: 100.0,
+            jump_speed: 200.0,
+            gravity_scale: 1.0,
+        });
+        movement
+            .modifiers
+            .extend(movement_modifier(PowerUpKind::Speed, 2));
+        movement
+            .modifiers
+            .extend(movement_modifier(PowerUpKind::LowGravity, 1));
+
+        let current = movement.current();
+        assert_eq!(current.speed, 100.0 * (1.0 + SPEED_BOOST_PER_STACK * 2.0));
+        assert_eq!(current.jump_speed, 200.0);
+        assert_eq!(current.gravity_scale, LOW_GRAVITY_SCALE);
+    }
+
+    #[test]
+    fn power_ups_leave_other_modifiers_alone() {
+        let mud = MovementModifier {
+            source: ModifierSource::Other("mud"),
+            speed: 0.5,
+            jump_speed: 1.0,
+            gravity_scale: 1.0,
+        };
+        let mut movement = Movement::new(MovementStats {
+            speed: 100.0,
+            jump_speed: 200.0,
+            gravity_scale: 1.0,
+        });
+        movement.modifiers.push(mud);
+        let mut active = ActivePowerUps::default();
+        let (duration, policy) = power_up_rules(PowerUpKind::Speed);
+        active.grant(PowerUpKind::Speed, duration, policy);
+
+        let mut world = World::new();
+        let player = world.spawn((movement, active)).id();
+        world.run_system_once(apply_power_up_effects).unwrap();
+        let modifiers = &world.get::<Movement>(player).unwrap().modifiers;
+        assert_eq!(modifiers.len(), 2);
+        assert!(modifiers.contains(&mud));
+
+        world.get_mut::<ActivePowerUps>(player).unwrap().0.clear();
+        world.run_system_once(apply_power_up_effects).unwrap();
+        assert_eq!(world.get::<Movement>(player).unwrap().modifiers, [mud]);
+    }
+}
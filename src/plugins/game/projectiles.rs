@@ -0,0 +1,319 @@
+//! Pooled projectiles for the player and enemies. Parked projectiles keep their entity with
+//! physics disabled, so bursts reuse entities instead of spawning new ones.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{
+        Enemy, Facing, Health, Player, PlayerInput, PooledProjectile, Projectile, Shoot, Shooter,
+        Team,
+    },
+    events::{Damage, FireProjectile, ProjectileHit},
+    plugins::physics::GameLayer,
+    states::GameState,
+};
+
+use super::constants::{PLAYER_SIZE, PROJECTILE_POOL_SIZE, PROJECTILE_Z};
+
+#[derive(Resource, Default)]
+pub struct ProjectilePool {
+    free: Vec<Entity>,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ProjectilePool>()
+        .add_observer(fire_projectile)
+        .add_systems(Startup, prewarm_pool)
+        .add_systems(OnExit(GameState::Playing), park_all_projectiles)
+        .add_systems(
+            FixedUpdate,
+            (
+                tick_shooters,
+                (player_shoot, enemy_shoot),
+                update_projectiles,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+}
+
+pub fn projectile_layers(team: Team) -> CollisionLayers {
+    match team {
+        Team::Player => CollisionLayers::new(
+            GameLayer::PlayerProjectile,
+            [GameLayer::Default, GameLayer::Enemy],
+        ),
+        Team::Enemy => CollisionLayers::new(
+            GameLayer::EnemyProjectile,
+            [GameLayer::Default, GameLayer::Player],
+        ),
+    }
+}
+
+fn parked_projectile() -> impl Bundle {
+    (
+        Name::new("Projectile"),
+        PooledProjectile,
+        Sprite::default(),
+        Transform::default(),
+        Visibility::Hidden,
+        RigidBody::Dynamic,
+        Collider::circle(1.0),
+        Sensor,
+        LinearVelocity::ZERO,
+        GravityScale(0.0),
+        CollidingEntities::default(),
+        RigidBodyDisabled,
+        ColliderDisabled,
+    )
+}
+
+fn prewarm_pool(mut commands: Commands, mut pool: ResMut<ProjectilePool>) {
+    for _ in 0..PROJECTILE_POOL_SIZE {
+        let entity = commands.spawn(parked_projectile()).id();
+        pool.free.push(entity);
+    }
+}
+
+fn fire_projectile(
+    fire: On<FireProjectile>,
+    mut commands: Commands,
+    mut pool: ResMut<ProjectilePool>,
+) {
+    // Only grow the pool when every projectile is already in flight.
+    let entity = pool
+        .free
+        .pop()
+        .unwrap_or_else(|| commands.spawn(parked_projectile()).id());
+    let spec = fire.spec;
+
+    commands
+        .entity(entity)
+        .remove::<(RigidBodyDisabled, ColliderDisabled)>()
+        .insert((
+            Projectile {
+                team: fire.team,
+                damage: spec.damage,
+                knockback: spec.knockback,
+                lifetime: Timer::from_seconds(spec.lifetime, TimerMode::Once),
+            },
+            Sprite {
+                color: spec.color,
+                custom_size: Some(Vec2::splat(spec.radius * 2.0)),
+                ..default()
+            },
+            Transform::from_translation(fire.origin.extend(PROJECTILE_Z)),
+            Visibility::Inherited,
+            Collider::circle(spec.radius),
+            projectile_layers(fire.team),
+            LinearVelocity(*fire.direction * spec.speed),
+            GravityScale(spec.gravity_scale),
+            CollidingEntities::default(),
+        ));
+}
+
+fn park(commands: &mut Commands, pool: &mut ProjectilePool, entity: Entity) {
+    commands.entity(entity).remove::<Projectile>().insert((
+        Visibility::Hidden,
+        LinearVelocity::ZERO,
+        RigidBodyDisabled,
+        ColliderDisabled,
+    ));
+    pool.free.push(entity);
+}
+
+/// The pool outlives levels, so in-flight projectiles are parked rather than despawned.
+fn park_all_projectiles(
+    mut commands: Commands,
+    mut pool: ResMut<ProjectilePool>,
+    projectiles: Query<Entity, With<Projectile>>,
+) {
+    for entity in projectiles.iter() {
+        park(&mut commands, &mut pool, entity);
+    }
+}
+
+fn tick_shooters(time: Res<Time>, mut shooters: Query<&mut Shooter>) {
+    for mut shooter in shooters.iter_mut() {
+        shooter.cooldown.tick(time.delta());
+    }
+}
+
+pub fn player_shoot(
+    mut commands: Commands,
+    mut players: Query<(&Actions<PlayerInput>, &Transform, &Facing, &mut Shooter), With<Player>>,
+    shoot_action: Query<&ActionState, With<Action<Shoot>>>,
+) {
+    for (actions, transform, facing, mut shooter) in players.iter_mut() {
+        let Some(shoot_state) = shoot_action.iter_many(actions.iter()).next() else {
+            continue;
+        };
+        if *shoot_state != ActionState::Fired || !shooter.ready() {
+            continue;
+        }
+
+        let direction = if facing.0 < 0.0 { Dir2::NEG_X } else { Dir2::X };
+        // Spawn just outside the player so the shot doesn't start inside its collider.
+        let offset = PLAYER_SIZE.x * 0.5 + shooter.spec.radius + 2.0;
+        commands.trigger(FireProjectile {
+            origin: transform.translation.xy() + *direction * offset,
+            direction,
+            team: shooter.team,
+            spec: shooter.spec,
+        });
+        shooter.cooldown.reset();
+    }
+}
+
+pub fn enemy_shoot(
+    mut commands: Commands,
+    mut enemies: Query<(&Transform, &mut Shooter), (With<Enemy>, Without<Player>)>,
+    players: Query<&Transform, With<Player>>,
+) {
+    for (transform, mut shooter) in enemies.iter_mut() {
+        if !shooter.ready() {
+            continue;
+        }
+        let origin = transform.translation.xy();
+        let target = players
+            .iter()
+            .map(|player| player.translation.xy())
+            .filter(|player| player.distance(origin) <= shooter.range)
+            .min_by(|a, b| a.distance(origin).total_cmp(&b.distance(origin)));
+        let Some(direction) = target.and_then(|target| Dir2::new(target - origin).ok()) else {
+            continue;
+        };
+
+        commands.trigger(FireProjectile {
+            origin,
+            direction,
+            team: shooter.team,
+            spec: shooter.spec,
+        });
+        shooter.cooldown.reset();
+    }
+}
+
+pub fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<ProjectilePool>,
+    mut projectiles: Query<(Entity, &mut Projectile, &Transform, &CollidingEntities)>,
+    solids: Query<(), Without<Sensor>>,
+    targets: Query<&Transform, With<Health>>,
+) {
+    for (entity, mut projectile, transform, colliding) in projectiles.iter_mut() {
+        // Sensors such as coins and hazard volumes don't stop projectiles.
+        let hit = colliding
+            .iter()
+            .copied()
+            .find(|&other| solids.contains(other));
+
+        if let Some(target) = hit {
+            let position = transform.translation.xy();
+            if let Ok(target_transform) = targets.get(target) {
+                let away = (target_transform.translation.x - position.x).signum();
+                commands.trigger(
+                    Damage::new(target, projectile.damage)
+                        .with_knockback(Vec2::new(away * projectile.knockback, 0.0)),
+                );
+            }
+            commands.trigger(ProjectileHit {
+                entity,
+                target,
+                position,
+            });
+            park(&mut commands, &mut pool, entity);
+        } else if projectile.lifetime.tick(time.delta()).is_finished() {
+            park(&mut commands, &mut pool, entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::game::constants::PLAYER_BULLET;
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ProjectilePool>()
+            .add_observer(fire_projectile);
+        app
+    }
+
+    fn fire(app: &mut App) {
+        app.world_mut().trigger(FireProjectile {
+            origin: Vec2::ZERO,
+            direction: Dir2::X,
+            team: Team::Player,
+            spec: PLAYER_BULLET,
+        });
+    }
+
+    fn pooled(app: &mut App) -> Vec<Entity> {
+        app.world_mut()
+            .query_filtered::<Entity, With<PooledProjectile>>()
+            .iter(app.world())
+            .collect()
+    }
+
+    #[test]
+    fn firing_reuses_a_parked_projectile() {
+        let mut app = setup();
+        let parked = app.world_mut().spawn(parked_projectile()).id();
+        app.world_mut()
+            .resource_mut::<ProjectilePool>()
+            .free
+            .push(parked);
+
+        fire(&mut app);
+
+        assert_eq!(pooled(&mut app), [parked]);
+        assert!(app.world().get::<Projectile>(parked).is_some());
+        assert!(app.world().get::<RigidBodyDisabled>(parked).is_none());
+        assert!(app.world().resource::<ProjectilePool>().free.is_empty());
+    }
+
+    #[test]
+    fn the_pool_grows_when_every_projectile_is_in_flight() {
+        let mut app = setup();
+        let parked = app.world_mut().spawn(parked_projectile()).id();
+        app.world_mut()
+            .resource_mut::<ProjectilePool>()
+            .free
+            .push(parked);
+
+        fire(&mut app);
+        fire(&mut app);
+
+        let projectiles = pooled(&mut app);
+        assert_eq!(projectiles.len(), 2);
+        assert!(projectiles.contains(&parked));
+        assert!(
+            projectiles
+                .iter()
+                .all(|&entity| app.world().get::<Projectile>(entity).is_some())
+        );
+    }
+
+    #[test]
+    fn projectiles_only_hit_the_other_team() {
+        let player = CollisionLayers::new(GameLayer::Player, LayerMask::ALL);
+        let enemy = CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL);
+        let ground = CollisionLayers::default();
+
+        let player_shot = projectile_layers(Team::Player);
+        assert!(!player_shot.interacts_with(player));
+        assert!(player_shot.interacts_with(enemy));
+        assert!(player_shot.interacts_with(ground));
+
+        let enemy_shot = projectile_layers(Team::Enemy);
+        assert!(enemy_shot.interacts_with(player));
+        assert!(!enemy_shot.interacts_with(enemy));
+        assert!(enemy_shot.interacts_with(ground));
+    }
+}
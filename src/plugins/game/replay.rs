@@ -0,0 +1,374 @@
+//! Deterministic input recording and replay. Each fixed tick, every player's action values are
+//! recorded into [`InputReplay`]; replaying feeds them back through [`ActionMock`] in place of
+//! live input. Player input is evaluated in `FixedPreUpdate` and everything that reads it runs
+//! in `FixedUpdate`, so the same ticks reproduce the same run. `FixedUpdate` runs on a single
+//! thread, so systems that don't order against each other still run in the same order every
+//! tick.
+
+use bevy::{
+    ecs::{query::QueryFilter, schedule::ExecutorKind},
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{InputDevices, Interact, Jump, Move, Player, PlayerInput, PlayerNumber, Shoot},
+    resources::{InputReplay, PlayerActions, Replay, RunSeed},
+    states::GameState,
+    storage,
+};
+
+use super::{level::LevelData, players::join_position, spawn::spawn_player};
+
+/// Storage key the last recorded run is saved under.
+pub const REPLAY_KEY: &str = "replay";
+
+pub(super) fn plugin(app: &mut App) {
+    app.edit_schedule(FixedUpdate, |schedule| {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    })
+    .init_resource::<InputReplay>()
+    .init_resource::<RunSeed>()
+    .add_systems(OnEnter(GameState::Playing), start_run)
+    .add_systems(OnExit(GameState::Playing), save_recording)
+    .add_systems(
+        FixedFirst,
+        (spawn_replay_players, mock_replay_input)
+            .chain()
+            .run_if(in_state(GameState::Playing).and(replaying)),
+    )
+    .add_systems(
+        FixedLast,
+        record_input.run_if(in_state(GameState::Playing).and(not(replaying))),
+    );
+}
+
+pub fn replaying(replay: Res<InputReplay>) -> bool {
+    replay.is_replaying()
+}
+
+/// Starts a fresh recording with a new seed, or rewinds the replay and restores its seed.
+fn start_run(
+    time: Res<Time<Real>>,
+    level: Res<LevelData>,
+    mut input_replay: ResMut<InputReplay>,
+    mut seed: ResMut<RunSeed>,
+) {
+    match &mut *input_replay {
+        InputReplay::Recording(replay) => {
+            seed.0 = time.elapsed().as_nanos() as u64;
+            *replay = Replay::new(level.id.clone(), seed.0);
+        }
+        InputReplay::Replaying { replay, tick } => {
+            *tick = 0;
+            seed.0 = replay.seed;
+            if replay.level != level.id {
+                warn!(
+                    "Replaying a run of {} on {}; it won't play out the same",
+                    replay.level, level.id
+                );
+            }
+            if replay.version != env!("CARGO_PKG_VERSION") {
+                warn!(
+                    "Replaying a run recorded by version {}, which may not play out the same",
+                    replay.version
+                );
+            }
+        }
+    }
+}
+
+fn save_recording(input_replay: Res<InputReplay>) {
+    let InputReplay::Recording(replay) = &*input_replay else {
+        return;
+    };
+    if replay.ticks.is_empty() {
+        return;
+    }
+    if let Err(error) = storage::save(REPLAY_KEY, &replay.to_text()) {
+        warn!("Couldn't save the replay: {error}");
+    }
+}
+
+fn fired<F: QueryFilter>(states: &Query<&ActionState, F>, context: &Actions<PlayerInput>) -> bool {
+    states
+        .iter_many(context.iter())
+        .any(|state| *state == ActionState::Fired)
+}
+
+fn record_input(
+    mut input_replay: ResMut<InputReplay>,
+    players: Query<(&PlayerNumber, &Actions<PlayerInput>), With<Player>>,
+    move_action: Query<&ActionValue, With<Action<Move>>>,
+    jump_action: Query<&ActionState, With<Action<Jump>>>,
+    shoot_action: Query<&ActionState, With<Action<Shoot>>>,
+    interact_action: Query<&ActionState, With<Action<Interact>>>,
+) {
+    let InputReplay::Recording(replay) = &mut *input_replay else {
+        return;
+    };
+    let mut tick = Vec::new();
+    for (number, context) in players.iter() {
+        if tick.len() <= number.0 {
+            tick.resize(number.0 + 1, None);
+        }
+        tick[number.0] = Some(PlayerActions {
+            movement: move_action
+                .iter_many(context.iter())
+                .next()
+                .map_or(Vec2::ZERO, |value| value.as_axis2d()),
+            jump: fired(&jump_action, context),
+            shoot: fired(&shoot_action, context),
+            interact: fired(&interact_action, context),
+        });
+    }
+    replay.ticks.push(tick);
+}
+
+/// Brings in players on the tick they joined in the recording. They have no devices, so only
+/// the replay drives them.
+fn spawn_replay_players(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    level: Res<LevelData>,
+    input_replay: Res<InputReplay>,
+    players: Query<(&PlayerNumber, &Transform), With<Player>>,
+) {
+    let InputReplay::Replaying { replay, tick } = &*input_replay else {
+        return;
+    };
+    let Some(recorded) = replay.ticks.get(*tick) else {
+        return;
+    };
+    let position = join_position(
+        players
+            .iter()
+            .map(|(number, transform)| (*number, transform)),
+        &level,
+    );
+    for (number, _) in recorded
+        .iter()
+        .enumerate()
+        .filter(|(_, actions)| actions.is_some())
+    {
+        let number = PlayerNumber(number);
+        if players.iter().all(|(other, _)| *other != number) {
+            spawn_player(
+                &mut commands,
+                &assets,
+                position,
+                number,
+                InputDevices::default(),
+            );
+        }
+    }
+}
+
+fn button_mock(held: bool) -> ActionMock {
+    let state = if held {
+        ActionState::Fired
+    } else {
+        ActionState::None
+    };
+    ActionMock::once(state, held)
+}
+
+/// Overrides each player's actions with this tick's recording, just before they're evaluated.
+/// Pause and restart stay live, so a replay can still be stopped.
+fn mock_replay_input(
+    mut commands: Commands,
+    mut input_replay: ResMut<InputReplay>,
+    players: Query<(&PlayerNumber, &Actions<PlayerInput>), With<Player>>,
+    actions: Query<(
+        Has<Action<Move>>,
+        Has<Action<Jump>>,
+        Has<Action<Shoot>>,
+        Has<Action<Interact>>,
+    )>,
+) {
+    let InputReplay::Replaying { replay, tick } = &mut *input_replay else {
+        return;
+    };
+    let Some(recorded) = replay.ticks.get(*tick) else {
+        return;
+    };
+    *tick += 1;
+
+    for (number, context) in players.iter() {
+        let recorded = recorded
+            .get(number.0)
+            .copied()
+            .flatten()
+            .unwrap_or_default();
+        for action in context.iter() {
+            let mock = match actions.get(action) {
+                Ok((true, ..)) => {
+                    let state = if recorded.movement == Vec2::ZERO {
+                        ActionState::None
+                    } else {
+                        ActionState::Fired
+                    };
+                    ActionMock::once(state, recorded.movement)
+                }
+                Ok((_, true, ..)) => button_mock(recorded.jump),
+                Ok((.., true, _)) => button_mock(recorded.shoot),
+                Ok((.., true)) => button_mock(recorded.interact),
+                _ => continue,
+            };
+            commands.entity(action).insert(mock);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Facing;
+    use crate::plugins::game::systems::apply_player_input;
+    use avian2d::prelude::*;
+    use bevy::{input::InputPlugin, state::app::StatesPlugin, time::TimeUpdateStrategy};
+    use std::time::Duration;
+
+    /// Stands in for a player at the keyboard: runs right for a while, then back left.
+    fn scripted_input(
+        mut commands: Commands,
+        mut tick: Local<u32>,
+        move_action: Query<Entity, With<Action<Move>>>,
+    ) {
+        let movement = if *tick % 40 < 25 {
+            Vec2::X
+        } else {
+            Vec2::NEG_X
+        };
+        *tick += 1;
+        for action in move_action.iter() {
+            commands
+                .entity(action)
+                .insert(ActionMock::once(ActionState::Fired, movement));
+        }
+    }
+
+    /// Moves bodies by their velocity, in place of physics.
+    fn integrate(time: Res<Time>, mut bodies: Query<(&mut Transform, &LinearVelocity)>) {
+        for (mut transform, velocity) in bodies.iter_mut() {
+            transform.translation += (velocity.0 * time.delta_secs()).extend(0.0);
+        }
+    }
+
+    fn run_app(input_replay: InputReplay) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            StatesPlugin,
+            InputPlugin,
+            EnhancedInputPlugin,
+            plugin,
+        ))
+        .init_asset::<Image>()
+        .init_state::<GameState>()
+        .init_resource::<LevelData>()
+        .insert_resource(input_replay)
+        // One fixed tick per update.
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 64.0,
+        )))
+        .add_input_context_to::<FixedPreUpdate, PlayerInput>()
+        .add_systems(FixedFirst, scripted_input.run_if(not(replaying)))
+        .add_systems(FixedUpdate, (apply_player_input, integrate).chain());
+        let assets = app.world().resource::<AssetServer>().clone();
+        let mut commands = app.world_mut().commands();
+        spawn_player(
+            &mut commands,
+            &assets,
+            Vec2::ZERO,
+            PlayerNumber(0),
+            InputDevices::default(),
+        );
+        app.world_mut().flush();
+        app
+    }
+
+    fn player_state(app: &mut App) -> (Vec3, f32) {
+        let (transform, facing) = app
+            .world_mut()
+            .query_filtered::<(&Transform, &Facing), With<Player>>()
+            .single(app.world())
+            .unwrap();
+        (transform.translation, facing.0)
+    }
+
+    fn actions(x: f32, jump: bool) -> Option<PlayerActions> {
+        Some(PlayerActions {
+            movement: Vec2::new(x, 0.0),
+            jump,
+            ..default()
+        })
+    }
+
+    #[test]
+    fn replay_round_trips_through_compact_text() {
+        let mut replay = Replay::new("starter", 42);
+        replay.ticks = vec![
+            vec![actions(0.0, false)],
+            vec![actions(0.0, false)],
+            vec![actions(0.0, false)],
+            vec![actions(-0.75, true), None, actions(1.0, false)],
+            vec![actions(-0.75, true), None, actions(1.0, false)],
+        ];
+
+        let text = replay.to_text();
+        assert_eq!(text.lines().count(), 3 + 4);
+        assert!(text.contains("-0.75,0J|-|1,0"));
+        let parsed = Replay::from_text(&text).unwrap();
+        assert_eq!(parsed.seed, 42);
+        assert_eq!(parsed, replay);
+    }
+
+    #[test]
+    fn malformed_replays_are_rejected() {
+        let header = "version 0.1.0\nlevel starter\nseed 7\n";
+
+        assert!(Replay::from_text(&format!("{header}0,0\n*2")).is_some());
+        assert!(Replay::from_text(&format!("{header}0,0X")).is_none());
+        assert!(Replay::from_text(&format!("{header}*2")).is_none());
+        assert!(Replay::from_text("level starter").is_none());
+        assert!(Replay::from_text("version 0.1.0\nlevel starter\nseed x").is_none());
+    }
+
+    #[test]
+    fn replaying_a_recording_ends_the_run_in_the_same_place() {
+        let mut recording = run_app(InputReplay::default());
+        for _ in 0..100 {
+            recording.update();
+        }
+        let recorded = player_state(&mut recording);
+        let text = recording
+            .world()
+            .resource::<InputReplay>()
+            .replay()
+            .to_text();
+
+        let replay = Replay::from_text(&text).unwrap();
+        assert!(replay.ticks.len() > 50);
+        let mut replaying = run_app(InputReplay::play(replay));
+        for _ in 0..200 {
+            let InputReplay::Replaying { replay, tick } =
+                replaying.world().resource::<InputReplay>()
+            else {
+                unreachable!();
+            };
+            if *tick >= replay.ticks.len() {
+                break;
+            }
+            replaying.update();
+        }
+
+        assert_ne!(recorded.0, Vec3::ZERO);
+        assert_eq!(player_state(&mut replaying), recorded);
+        assert_eq!(
+            replaying.world().resource::<RunSeed>(),
+            recording.world().resource::<RunSeed>()
+        );
+    }
+}
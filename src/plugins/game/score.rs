@@ -0,0 +1,282 @@
+//! Scoring: combo-multiplied pickups, end-of-level bonuses and the persisted high-score table.
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    components::{CollectibleKind, Goal, Invulnerable, Player, ScoreText, Shielded},
+    events::{CoinCollected, Collected, Damage},
+    plugins::fonts::FontAssets,
+    resources::{CollectibleKinds, HighScoreEntry, HighScores, LevelResult, Score, ScoreBonus},
+    states::GameState,
+    storage,
+    utils::format_score,
+};
+
+use super::{
+    constants::{
+        COMBO_MAX_MULTIPLIER, COMBO_WINDOW_SECONDS, GOAL_COLOR, GOAL_SIZE, HIGH_SCORE_ENTRIES,
+        HUD_TEXT_COLOR, NO_DAMAGE_BONUS, TIME_BONUS_PER_SECOND,
+    },
+    level::LevelData,
+};
+
+const HIGH_SCORES_KEY: &str = "high_scores";
+
+pub(super) fn plugin(app: &mut App) {
+    let high_scores = storage::load(HIGH_SCORES_KEY)
+        .map(|text| HighScores::from_text(&text))
+        .unwrap_or_default();
+    app.insert_resource(high_scores)
+        .init_resource::<Score>()
+        .init_resource::<LevelResult>()
+        .add_observer(score_pickup)
+        .add_observer(score_coin)
+        .add_observer(track_damage_taken)
+        .add_systems(
+            FixedUpdate,
+            (tick_score, reach_goal).run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            update_score_text.run_if(in_state(GameState::Playing)),
+        );
+}
+
+pub fn spawn_goal(commands: &mut Commands, position: Vec2) {
+    commands.spawn((
+        Name::new("Goal"),
+        Goal,
+        DespawnOnExit(GameState::Playing),
+        Sprite {
+            color: GOAL_COLOR,
+            custom_size: Some(GOAL_SIZE),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.0)),
+        RigidBody::Static,
+        Collider::rectangle(GOAL_SIZE.x, GOAL_SIZE.y),
+        Sensor,
+        CollidingEntities::default(),
+    ));
+}
+
+pub fn spawn_score_text(commands: &mut Commands, fonts: &FontAssets) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        DespawnOnExit(GameState::Playing),
+        children![(
+            Text::new(format_score(0, 1)),
+            TextFont {
+                font: fonts.default.clone(),
+                font_size: 42.0,
+                font_smoothing: FontSmoothing::None,
+                ..default()
+            },
+            TextColor(HUD_TEXT_COLOR),
+            ScoreText,
+        )],
+    ));
+}
+
+/// Combo multiplier for the given chain length.
+pub fn combo_multiplier(combo: u32) -> u32 {
+    combo.clamp(1, COMBO_MAX_MULTIPLIER)
+}
+
+/// Adds `points` at the current combo multiplier. Each pickup inside the window extends the chain.
+fn add_pickup_points(score: &mut Score, points: u32) {
+    score.combo = if score.combo_timer.is_finished() {
+        1
+    } else {
+        score.combo + 1
+    };
+    score.combo_timer = Timer::from_seconds(COMBO_WINDOW_SECONDS, TimerMode::Once);
+    score.points += points * combo_multiplier(score.combo);
+}
+
+/// Coins are scored by [`score_coin`].
+fn score_pickup(collected: On<Collected>, kinds: Res<CollectibleKinds>, mut score: ResMut<Score>) {
+    if collected.kind == CollectibleKind::COIN {
+        return;
+    }
+    let Some(info) = kinds.get(collected.kind) else {
+        return;
+    };
+    add_pickup_points(&mut score, info.points * collected.value);
+}
+
+fn score_coin(
+    collected: On<CoinCollected>,
+    kinds: Res<CollectibleKinds>,
+    mut score: ResMut<Score>,
+) {
+    let Some(info) = kinds.get(CollectibleKind::COIN) else {
+        return;
+    };
+    add_pickup_points(&mut score, info.points * collected.value);
+}
+
+fn track_damage_taken(
+    damage: On<Damage>,
+    mut score: ResMut<Score>,
+    players: Query<(Has<Invulnerable>, Has<Shielded>), With<Player>>,
+) {
+    let Ok((invulnerable, shielded)) = players.get(damage.entity) else {
+        return;
+    };
+    if damage.lethal || !(invulnerable || shielded) {
+        score.damaged = true;
+    }
+}
+
+fn tick_score(time: Res<Time>, mut score: ResMut<Score>) {
+    score.level_secs += time.delta_secs();
+    if score.combo > 0 && score.combo_timer.tick(time.delta()).is_finished() {
+        score.combo = 0;
+    }
+}
+
+/// End-of-level bonuses for finishing under par and without taking a hit.
+pub fn level_bonuses(score: &Score, par_secs: f32) -> Vec<ScoreBonus> {
+    let mut bonuses = Vec::new();
+    let secs_under_par = (par_secs - score.level_secs).max(0.0) as u32;
+    if secs_under_par > 0 {
+        bonuses.push(ScoreBonus {
+            label: "Time",
+            points: secs_under_par * TIME_BONUS_PER_SECOND,
+        });
+    }
+    if !score.damaged {
+        bonuses.push(ScoreBonus {
+            label: "No damage",
+            points: NO_DAMAGE_BONUS,
+        });
+    }
+    bonuses
+}
+
+fn reach_goal(
+    mut commands: Commands,
+    level: Res<LevelData>,
+    score: Res<Score>,
+    mut high_scores: ResMut<HighScores>,
+    mut next_state: ResMut<NextState<GameState>>,
+    goals: Query<&CollidingEntities, With<Goal>>,
+    players: Query<(), With<Player>>,
+) {
+    let reached = goals
+        .iter()
+        .any(|colliding| colliding.iter().any(|&entity| players.contains(entity)));
+    if !reached {
+        return;
+    }
+
+    let bonuses = level_bonuses(&score, level.par_secs);
+    let total = score.points + bonuses.iter().map(|bonus| bonus.points).sum::<u32>();
+    let rank = high_scores.insert(
+        &level.id,
+        HighScoreEntry {
+            points: total,
+            secs: score.level_secs,
+        },
+        HIGH_SCORE_ENTRIES,
+    );
+    if rank.is_some() {
+        if let Err(error) = storage::save(HIGH_SCORES_KEY, &high_scores.to_text()) {
+            warn!("Couldn't save high scores: {error}");
+        }
+    }
+
+    commands.insert_resource(LevelResult {
+        level: level.id.clone(),
+        pickups: score.points,
+        bonuses,
+        total,
+        secs: score.level_secs,
+        rank,
+    });
+    next_state.set(GameState::LevelComplete);
+}
+
+fn update_score_text(score: Res<Score>, mut text: Query<&mut Text, With<ScoreText>>) {
+    if !score.is_changed() {
+        return;
+    }
+
+    for mut text in text.iter_mut() {
+        text.0 = format_score(score.points, combo_multiplier(score.combo));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn high_scores_keep_the_best_entries_in_order() {
+        let mut scores = HighScores::default();
+        for points in [300, 100, 500, 200, 400, 50] {
+            scores.insert("level", HighScoreEntry { points, secs: 10.0 }, 5);
+        }
+
+        let points: Vec<_> = scores
+            .get("level")
+            .iter()
+            .map(|entry| entry.points)
+            .collect();
+        assert_eq!(points, [500, 400, 300, 200, 100]);
+        assert_eq!(
+            scores.insert(
+                "level",
+                HighScoreEntry {
+                    points: 10,
+                    secs: 1.0
+                },
+                5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn high_scores_round_trip_through_text() {
+        let mut scores = HighScores::default();
+        scores.insert(
+            "a",
+            HighScoreEntry {
+                points: 1200,
+                secs: 42.5,
+            },
+            5,
+        );
+        scores.insert(
+            "b",
+            HighScoreEntry {
+                points: 300,
+                secs: 12.25,
+            },
+            5,
+        );
+
+        assert_eq!(HighScores::from_text(&scores.to_text()), scores);
+    }
+
+    #[test]
+    fn bonuses_reward_speed_and_no_damage() {
+        let score = Score {
+            level_secs: 50.5,
+            ..default()
+        };
+
+        let bonuses = level_bonuses(&score, 60.0);
+        assert_eq!(bonuses[0].points, 9 * TIME_BONUS_PER_SECOND);
+        assert_eq!(bonuses[1].points, NO_DAMAGE_BONUS);
+    }
+}
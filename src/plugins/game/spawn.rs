@@ -3,38 +3,71 @@ use bevy::{prelude::*, text::FontSmoothing};
 use bevy_enhanced_input::prelude::*;
 
 use crate::{
-    components::{Coin, CoinSlot, Ground, HudText, Jump, Move, Platform, Player, PlayerInput},
-    plugins::fonts::FontAssets,
+    components::{
+        ActivePowerUps, CoinCounter, CoinCounterText, CoinSlot, CollectibleKind, Facing, Ground,
+        Health, HudText, InputDevices, Interact, Jump, KeyRing, LivesText, Move, Movement,
+        MovementStats, Pause, Platform, Player, PlayerInput, PlayerNumber, Restart, Shoot, Shooter,
+        Surroundings, Team,
+    },
+    plugins::{
+        camera::CameraTarget, fonts::FontAssets, input::control_prompts, physics::GameLayer,
+    },
+    resources::{CoinState, CollectibleKinds, Lives},
+    states::GameState,
+    utils::{format_coin_count, format_lives},
 };
 
-use super::constants::{
-    COIN_ICON_SIZE, COIN_RADIUS, COIN_SIZE, COIN_SLOT_EMPTY_COLOR, COIN_SLOTS, HUD_TEXT_COLOR,
-    PLATFORM_SIZE, PLAYER_SIZE, SMALL_PLATFORM_SIZE,
+use super::{
+    constants::{
+        COIN_ICON_SIZE, COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, COIN_SLOT_LIMIT,
+        HUD_TEXT_COLOR, JUMP_SPEED, PLAYER_BULLET, PLAYER_COLORS, PLAYER_MAX_HEALTH,
+        PLAYER_SHOOT_COOLDOWN, PLAYER_SIZE, PLAYER_SPEED,
+    },
+    level::PlatformSpec,
 };
 
-#[derive(Clone, Copy)]
-struct PlatformSpec {
+pub fn spawn_player(
+    commands: &mut Commands,
+    assets: &AssetServer,
     position: Vec2,
-    size: Vec2,
-    color: Color,
-}
-
-pub fn spawn_player(commands: &mut Commands, assets: &AssetServer) {
+    number: PlayerNumber,
+    devices: InputDevices,
+) -> Entity {
     let player_texture = assets.load("textures/bevy.png");
     let player = commands
         .spawn((
             Player,
+            number,
+            devices,
+            Name::new(format!("Player {}", number.label())),
             PlayerInput,
-            Transform::from_xyz(0.0, 0.0, 0.0),
+            DespawnOnExit(GameState::Playing),
+            Health::new(PLAYER_MAX_HEALTH),
+            Surroundings::new(PLAYER_SIZE),
+            Facing::default(),
+            Movement::new(MovementStats {
+                speed: PLAYER_SPEED,
+                jump_speed: JUMP_SPEED,
+                gravity_scale: 1.0,
+            }),
+            ActivePowerUps::default(),
+            KeyRing::default(),
+            CameraTarget::default(),
+            Shooter::new(Team::Player, PLAYER_BULLET, PLAYER_SHOOT_COOLDOWN, 0.0),
+            Transform::from_translation(position.extend(0.0)),
             Visibility::default(),
             RigidBody::Dynamic,
             Collider::rectangle(PLAYER_SIZE.x, PLAYER_SIZE.y),
             LinearVelocity::ZERO,
+            GravityScale(1.0),
             // Keep wall sliding smooth when pushing into vertical surfaces.
             Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
             Restitution::ZERO,
             LockedAxes::ROTATION_LOCKED,
+            CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
             CollidingEntities::default(),
+            // Gameplay bindings are filled in from `ControlBindings`, and rebuilt when they
+            // change. Pause and restart aren't rebindable.
             actions!(PlayerInput[
                 (
                     Action::<Move>::new(),
@@ -42,20 +75,17 @@ pub fn spawn_player(commands: &mut Commands, assets: &AssetServer) {
                         consume_input: false,
                         ..default()
                     },
-                    Bindings::spawn((
-                        Cardinal::wasd_keys(),
-                        Cardinal::arrows(),
-                        Axial::left_stick(),
-                    )),
+                ),
+                Action::<Jump>::new(),
+                Action::<Shoot>::new(),
+                Action::<Interact>::new(),
+                (
+                    Action::<Pause>::new(),
+                    bindings![KeyCode::Escape, KeyCode::KeyP, GamepadButton::Start],
                 ),
                 (
-                    Action::<Jump>::new(),
-                    bindings![
-                        KeyCode::Space,
-                        KeyCode::KeyW,
-                        KeyCode::ArrowUp,
-                        GamepadButton::South
-                    ],
+                    Action::<Restart>::new(),
+                    bindings![KeyCode::KeyR, GamepadButton::Select],
                 ),
             ]),
         ))
@@ -76,35 +106,21 @@ pub fn spawn_player(commands: &mut Commands, assets: &AssetServer) {
     commands.spawn((
         Sprite {
             image: player_texture,
+            color: PLAYER_COLORS[number.0 % PLAYER_COLORS.len()],
             custom_size: Some(PLAYER_SIZE),
             ..default()
         },
         Transform::from_xyz(0.0, 0.0, 1.0),
         ChildOf(player),
     ));
+    player
 }
 
-pub fn spawn_platforms(commands: &mut Commands) {
-    let platforms = [
-        PlatformSpec {
-            position: Vec2::new(0.0, -140.0),
-            size: PLATFORM_SIZE,
-            color: Color::srgb(0.2, 0.2, 0.25),
-        },
-        PlatformSpec {
-            position: Vec2::new(140.0, -20.0),
-            size: SMALL_PLATFORM_SIZE,
-            color: Color::srgb(0.25, 0.25, 0.3),
-        },
-        PlatformSpec {
-            position: Vec2::new(-160.0, 60.0),
-            size: SMALL_PLATFORM_SIZE,
-            color: Color::srgb(0.25, 0.25, 0.3),
-        },
-    ];
+pub fn spawn_platforms(commands: &mut Commands, platforms: &[PlatformSpec]) {
     for platform in platforms {
         commands.spawn((
             Ground,
+            DespawnOnExit(GameState::Playing),
             Platform {
                 size: platform.size,
             },
@@ -120,31 +136,6 @@ pub fn spawn_platforms(commands: &mut Commands) {
     }
 }
 
-pub fn spawn_coins(commands: &mut Commands, assets: &AssetServer) {
-    let coin_texture = assets.load("textures/ferris.png");
-    let base_left = -PLATFORM_SIZE.x * 0.5 + COIN_SIZE.x * 0.5 + 8.0;
-    let coins = [
-        Vec2::new(base_left, -100.0),
-        Vec2::new(-160.0, 110.0),
-        Vec2::new(140.0, 20.0),
-    ];
-
-    for position in coins {
-        commands.spawn((
-            Coin,
-            Sprite {
-                image: coin_texture.clone(),
-                custom_size: Some(COIN_SIZE),
-                ..default()
-            },
-            Transform::from_xyz(position.x, position.y, 1.0),
-            RigidBody::Static,
-            Collider::circle(COIN_RADIUS),
-            Sensor,
-        ));
-    }
-}
-
 pub fn spawn_hud(commands: &mut Commands, fonts: &FontAssets) {
     commands.spawn((
         Node {
@@ -162,12 +153,29 @@ pub fn spawn_hud(commands: &mut Commands, fonts: &FontAssets) {
         },
         TextColor(HUD_TEXT_COLOR),
         HudText,
+        DespawnOnExit(GameState::Playing),
+    ));
+    commands.spawn((
+        Name::new("ControlPrompts"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        DespawnOnExit(GameState::Playing),
+        children![control_prompts()],
     ));
 }
 
-pub fn spawn_coin_counter(commands: &mut Commands, assets: &AssetServer) {
-    let coin_texture = assets.load("textures/ferris.png");
-
+/// The lives, followed by the coin slots if the coin kind has a HUD counter.
+pub fn spawn_coin_counter(
+    commands: &mut Commands,
+    fonts: &FontAssets,
+    lives: &Lives,
+    kinds: &CollectibleKinds,
+) {
     let root = commands
         .spawn((
             Node {
@@ -180,10 +188,58 @@ pub fn spawn_coin_counter(commands: &mut Commands, assets: &AssetServer) {
                 ..default()
             },
             Name::new("CoinCounter"),
+            DespawnOnExit(GameState::Playing),
         ))
         .id();
 
-    for index in 0..COIN_SLOTS {
+    commands.spawn((
+        Text::new(format_lives(lives)),
+        hud_font(fonts),
+        TextColor(HUD_TEXT_COLOR),
+        LivesText,
+        ChildOf(root),
+    ));
+
+    if !kinds
+        .get(CollectibleKind::COIN)
+        .is_some_and(|info| info.hud_counter)
+    {
+        return;
+    }
+    // Filled in by `rebuild_coin_counter` once the level's coins have spawned.
+    commands.spawn((
+        Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(12.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        CoinCounter::default(),
+        ChildOf(root),
+    ));
+}
+
+/// One slot per coin, or an "x / y" readout when they wouldn't fit.
+pub fn spawn_coin_slots(
+    commands: &mut Commands,
+    assets: &AssetServer,
+    fonts: &FontAssets,
+    counter: Entity,
+    coin_state: &CoinState,
+) {
+    if coin_state.total > COIN_SLOT_LIMIT {
+        commands.spawn((
+            Text::new(format_coin_count(coin_state.arrived, coin_state.total)),
+            hud_font(fonts),
+            TextColor(HUD_TEXT_COLOR),
+            CoinCounterText,
+            ChildOf(counter),
+        ));
+        return;
+    }
+
+    let coin_texture = assets.load("textures/ferris.png");
+    for index in 0..coin_state.total {
         commands.spawn((
             Node {
                 width: Val::Px(COIN_ICON_SIZE.x),
@@ -192,11 +248,24 @@ pub fn spawn_coin_counter(commands: &mut Commands, assets: &AssetServer) {
             },
             ImageNode {
                 image: coin_texture.clone(),
-                color: COIN_SLOT_EMPTY_COLOR,
+                color: if index < coin_state.arrived {
+                    COIN_SLOT_FILLED_COLOR
+                } else {
+                    COIN_SLOT_EMPTY_COLOR
+                },
                 ..default()
             },
             CoinSlot { index },
-            ChildOf(root),
+            ChildOf(counter),
         ));
     }
 }
+
+fn hud_font(fonts: &FontAssets) -> TextFont {
+    TextFont {
+        font: fonts.default.clone(),
+        font_size: 42.0,
+        font_smoothing: FontSmoothing::None,
+        ..default()
+    }
+}
@@ -0,0 +1,52 @@
+//! Ground and wall detection shared by everything that walks on platforms.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::components::{Ground, GroundContact, Platform, Surroundings};
+
+pub fn detect_surroundings(
+    mut movers: Query<(&Transform, &CollidingEntities, &mut Surroundings)>,
+    platforms: Query<(&Transform, &Platform), With<Ground>>,
+) {
+    for (transform, colliding, mut surroundings) in movers.iter_mut() {
+        let position = transform.translation.xy();
+        let half_size = surroundings.half_size;
+        let bottom = position.y - half_size.y;
+        let top = position.y + half_size.y;
+
+        let mut ground = None;
+        let mut wall_left = false;
+        let mut wall_right = false;
+        for &entity in colliding.iter() {
+            let Ok((platform_transform, platform)) = platforms.get(entity) else {
+                continue;
+            };
+            let center = platform_transform.translation.xy();
+            let half = platform.size * 0.5;
+            let platform_top = center.y + half.y;
+            let platform_bottom = center.y - half.y;
+
+            // Consider grounded only when the mover is above the platform top.
+            if bottom >= platform_top - 1.0 {
+                ground = Some(GroundContact {
+                    entity,
+                    left: center.x - half.x,
+                    right: center.x + half.x,
+                    top: platform_top,
+                });
+            } else if top > platform_bottom + 1.0 {
+                // Overlapping vertically, so this is a side contact rather than a head bump.
+                if center.x < position.x {
+                    wall_left = true;
+                } else {
+                    wall_right = true;
+                }
+            }
+        }
+
+        surroundings.ground = ground;
+        surroundings.wall_left = wall_left;
+        surroundings.wall_right = wall_right;
+    }
+}
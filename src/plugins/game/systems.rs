@@ -6,66 +6,68 @@ use bevy::{
 use bevy_enhanced_input::prelude::*;
 
 use crate::{
-    components::{Coin, CoinSlot, Ground, HudText, Jump, Move, Platform, Player},
-    resources::{CoinState, FpsDisplay},
-    utils::format_hud_text,
+    components::{
+        CoinCounter, CoinCounterText, CoinSlot, Facing, Health, HudText, Jump, LivesText, Move,
+        Movement, Player, PlayerInput, PlayerNumber, Staggered, Surroundings,
+    },
+    plugins::fonts::FontAssets,
+    resources::{CoinState, FpsDisplay, Lives},
+    utils::{format_coin_count, format_hud_text, format_lives, format_player_hud},
 };
 
-use super::constants::{
-    COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, COIN_SLOTS, JUMP_SPEED, PLAYER_SIZE,
-    PLAYER_SPEED,
-};
+use super::constants::{COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR};
+use super::spawn::spawn_coin_slots;
 
 pub fn apply_player_input(
-    mut players: Query<(&Transform, &mut LinearVelocity, &CollidingEntities), With<Player>>,
+    mut players: Query<
+        (
+            &Actions<PlayerInput>,
+            &mut LinearVelocity,
+            &mut GravityScale,
+            &mut Facing,
+            &Movement,
+            &Surroundings,
+            Has<Staggered>,
+        ),
+        With<Player>,
+    >,
     move_action: Query<&ActionValue, With<Action<Move>>>,
     jump_action: Query<&ActionState, With<Action<Jump>>>,
-    ground: Query<(&Transform, &Platform), With<Ground>>,
 ) {
-    let Ok((player_transform, mut velocity, colliding)) = players.single_mut() else {
-        return;
-    };
-    let Ok(move_value) = move_action.single() else {
-        return;
-    };
-    let Ok(jump_state) = jump_action.single() else {
-        return;
-    };
-
-    let movement = move_value.as_axis2d();
-    velocity.x = movement.x * PLAYER_SPEED;
-
-    let player_bottom = player_transform.translation.y - PLAYER_SIZE.y * 0.5;
-    // Consider grounded only when the player is above the platform top.
-    let grounded = colliding.iter().any(|entity| {
-        let Ok((platform_transform, platform)) = ground.get(*entity) else {
-            return false;
+    for (
+        actions,
+        mut velocity,
+        mut gravity,
+        mut facing,
+        player_movement,
+        surroundings,
+        staggered,
+    ) in players.iter_mut()
+    {
+        let stats = player_movement.current();
+        gravity.0 = stats.gravity_scale;
+        // Let knockback play out before handing control back.
+        if staggered {
+            continue;
+        }
+        // Each player only reads the actions of its own context.
+        let Some(move_value) = move_action.iter_many(actions.iter()).next() else {
+            continue;
         };
-        let platform_top = platform_transform.translation.y + platform.size.y * 0.5;
-        player_bottom >= platform_top - 1.0
-    });
-    // Jump only when grounded and the action is fired.
-    if grounded && *jump_state == ActionState::Fired {
-        velocity.y = JUMP_SPEED;
-    }
-}
-
-pub fn collect_coins(
-    mut commands: Commands,
-    mut coin_state: ResMut<CoinState>,
-    players: Query<&CollidingEntities, With<Player>>,
-    coins: Query<Entity, With<Coin>>,
-) {
-    let Ok(colliding) = players.single() else {
-        return;
-    };
-
-    for entity in colliding.iter() {
-        let Ok(coin_entity) = coins.get(*entity) else {
+        let Some(jump_state) = jump_action.iter_many(actions.iter()).next() else {
             continue;
         };
-        commands.entity(coin_entity).despawn();
-        coin_state.collected = (coin_state.collected + 1).min(COIN_SLOTS);
+
+        let movement = move_value.as_axis2d();
+        velocity.x = movement.x * stats.speed;
+        if movement.x != 0.0 {
+            facing.0 = movement.x.signum();
+        }
+
+        // Jump only when grounded and the action is fired.
+        if surroundings.is_grounded() && *jump_state == ActionState::Fired {
+            velocity.y = stats.jump_speed;
+        }
     }
 }
 
@@ -73,16 +75,12 @@ pub fn update_hud(
     time: Res<Time>,
     diagnostics: Res<DiagnosticsStore>,
     mut fps_display: ResMut<FpsDisplay>,
+    coin_state: Res<CoinState>,
     move_action: Query<&ActionValue, With<Action<Move>>>,
     jump_action: Query<&ActionState, With<Action<Jump>>>,
+    players: Query<(&PlayerNumber, &Health, &Actions<PlayerInput>), With<Player>>,
     mut text: Query<&mut Text, With<HudText>>,
 ) {
-    let Ok(move_value) = move_action.single() else {
-        return;
-    };
-    let Ok(jump_state) = jump_action.single() else {
-        return;
-    };
     let Ok(mut text) = text.single_mut() else {
         return;
     };
@@ -95,23 +93,69 @@ pub fn update_hud(
             .unwrap_or(0.0);
     }
 
-    let movement = move_value.as_axis2d();
-    text.0 = format_hud_text(fps_display.value, movement, *jump_state);
+    let mut players: Vec<_> = players.iter().collect();
+    players.sort_by_key(|(number, ..)| **number);
+    let lines: Vec<_> = players
+        .into_iter()
+        .filter_map(|(number, health, actions)| {
+            let move_value = move_action.iter_many(actions.iter()).next()?;
+            let jump_state = jump_action.iter_many(actions.iter()).next()?;
+            Some(format_player_hud(
+                *number,
+                move_value.as_axis2d(),
+                *jump_state,
+                health,
+                coin_state.collected_by(number.0),
+            ))
+        })
+        .collect();
+    text.0 = format_hud_text(fps_display.value, &lines);
+}
+
+pub fn rebuild_coin_counter(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    fonts: Res<FontAssets>,
+    coin_state: Res<CoinState>,
+    mut counters: Query<(Entity, &mut CoinCounter)>,
+) {
+    for (entity, mut counter) in counters.iter_mut() {
+        if counter.total == coin_state.total {
+            continue;
+        }
+        counter.total = coin_state.total;
+        commands.entity(entity).despawn_related::<Children>();
+        spawn_coin_slots(&mut commands, &assets, &fonts, entity, &coin_state);
+    }
 }
 
 pub fn update_coin_counter(
     coin_state: Res<CoinState>,
     mut slots: Query<(&CoinSlot, &mut ImageNode)>,
+    mut texts: Query<&mut Text, With<CoinCounterText>>,
 ) {
     if !coin_state.is_changed() {
         return;
     }
 
     for (slot, mut image) in slots.iter_mut() {
-        image.color = if slot.index < coin_state.collected {
+        image.color = if slot.index < coin_state.arrived {
             COIN_SLOT_FILLED_COLOR
         } else {
             COIN_SLOT_EMPTY_COLOR
         };
     }
+    for mut text in texts.iter_mut() {
+        text.0 = format_coin_count(coin_state.arrived, coin_state.total);
+    }
+}
+
+pub fn update_lives_text(lives: Res<Lives>, mut text: Query<&mut Text, With<LivesText>>) {
+    if !lives.is_changed() {
+        return;
+    }
+
+    for mut text in text.iter_mut() {
+        text.0 = format_lives(&lives);
+    }
 }
@@ -0,0 +1,318 @@
+//! Control bindings. Defaults come from a config asset, the player's own bindings are saved
+//! over them, and whichever is live is applied to every `PlayerInput` context when it changes,
+//! without respawning anything. Each context only gets the inputs of the devices its player owns.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::relationship::RelatedSpawner,
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{InputDevices, Interact, Jump, Move, PlayerInput, Shoot},
+    events::{BindingConflict, RebindControl, ResetBindings},
+    resources::{Control, ControlBindings, InputBinding},
+    storage,
+};
+
+const BINDINGS_KEY: &str = "bindings";
+const DEFAULT_BINDINGS: &str = "config/default.bindings";
+
+/// Where the live bindings came from.
+#[derive(Resource)]
+struct BindingsSource {
+    config: Handle<ControlBindings>,
+    /// Whether the player has rebound anything, in which case config changes are ignored.
+    customized: bool,
+}
+
+/// Loads `.bindings` files written by [`ControlBindings::to_text`].
+#[derive(TypePath, Default)]
+struct BindingsLoader;
+
+impl AssetLoader for BindingsLoader {
+    type Asset = ControlBindings;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<ControlBindings, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ControlBindings::from_text(std::str::from_utf8(&bytes)?))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bindings"]
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<ControlBindings>()
+        .register_asset_loader(BindingsLoader)
+        .init_resource::<ControlBindings>()
+        .add_observer(rebind_control)
+        .add_observer(reset_bindings)
+        .add_systems(Startup, load_bindings)
+        .add_systems(Update, (apply_config, rebuild_player_bindings).chain());
+}
+
+fn load_bindings(mut commands: Commands, assets: Res<AssetServer>) {
+    let saved = storage::load(BINDINGS_KEY).map(|text| ControlBindings::from_text(&text));
+    commands.insert_resource(BindingsSource {
+        config: assets.load(DEFAULT_BINDINGS),
+        customized: saved.is_some(),
+    });
+    if let Some(saved) = saved {
+        commands.insert_resource(saved);
+    }
+}
+
+/// Uses the config asset once it loads, and again whenever it's edited, unless the player has
+/// their own bindings.
+fn apply_config(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<ControlBindings>>,
+    source: Res<BindingsSource>,
+    configs: Res<Assets<ControlBindings>>,
+    mut bindings: ResMut<ControlBindings>,
+) {
+    let changed = events.read().any(|event| {
+        event.is_loaded_with_dependencies(&source.config) || event.is_modified(&source.config)
+    });
+    if !changed || source.customized {
+        return;
+    }
+    let Some(config) = configs.get(&source.config) else {
+        return;
+    };
+    *bindings = config.clone();
+    for conflict in bindings.conflicts() {
+        warn!(
+            "{} is bound to both {} and {}",
+            conflict.binding,
+            conflict.existing.label(),
+            conflict.requested.label()
+        );
+        commands.trigger(conflict);
+    }
+}
+
+fn rebind_control(
+    rebind: On<RebindControl>,
+    mut commands: Commands,
+    mut bindings: ResMut<ControlBindings>,
+    mut source: ResMut<BindingsSource>,
+) {
+    if let Err(conflict) = bindings.rebind(rebind.control, rebind.binding) {
+        commands.trigger(conflict);
+        return;
+    }
+    source.customized = true;
+    if let Err(error) = storage::save(BINDINGS_KEY, &bindings.to_text()) {
+        warn!("Couldn't save bindings: {error}");
+    }
+}
+
+fn reset_bindings(
+    _reset: On<ResetBindings>,
+    mut bindings: ResMut<ControlBindings>,
+    mut source: ResMut<BindingsSource>,
+    configs: Res<Assets<ControlBindings>>,
+) {
+    source.customized = false;
+    if let Err(error) = storage::remove(BINDINGS_KEY) {
+        warn!("Couldn't remove saved bindings: {error}");
+    }
+    if let Some(config) = configs.get(&source.config) {
+        *bindings = config.clone();
+    }
+}
+
+fn binding(input: InputBinding) -> Binding {
+    match input {
+        InputBinding::Key(key) => key.into(),
+        InputBinding::Gamepad(button) => button.into(),
+    }
+}
+
+/// Whether a context driven by `devices` can use `input`. Contexts without devices take any.
+fn usable(devices: Option<InputDevices>, input: InputBinding) -> bool {
+    let Some(devices) = devices else {
+        return true;
+    };
+    match input {
+        InputBinding::Key(key) => devices.keyboard.is_some_and(|half| half.contains(key)),
+        InputBinding::Gamepad(_) => devices.gamepad.is_some(),
+    }
+}
+
+/// Replaces the bindings of every action in new or changed `PlayerInput` contexts, and in those
+/// whose player changed devices.
+fn rebuild_player_bindings(
+    mut commands: Commands,
+    bindings: Res<ControlBindings>,
+    contexts: Query<(Entity, Ref<Actions<PlayerInput>>, Option<Ref<InputDevices>>)>,
+    actions: Query<(
+        Has<Action<Move>>,
+        Has<Action<Jump>>,
+        Has<Action<Shoot>>,
+        Has<Action<Interact>>,
+    )>,
+) {
+    for (player, context, devices) in contexts.iter() {
+        let devices_changed = devices.as_ref().is_some_and(DetectChanges::is_changed);
+        if !bindings.is_changed() && !context.is_added() && !devices_changed {
+            continue;
+        }
+        let devices = devices.map(|devices| *devices);
+        let gamepad = match devices {
+            None => GamepadDevice::Any,
+            Some(InputDevices {
+                gamepad: Some(gamepad),
+                ..
+            }) => GamepadDevice::Single(gamepad),
+            Some(_) => GamepadDevice::None,
+        };
+        commands.entity(player).insert(gamepad);
+        let inputs = |control| -> Vec<_> {
+            bindings
+                .get(control)
+                .iter()
+                .copied()
+                .filter(|&input| usable(devices, input))
+                .collect()
+        };
+
+        for action in context.iter() {
+            let control = match actions.get(action) {
+                Ok((true, ..)) => None,
+                Ok((_, true, ..)) => Some(Control::Jump),
+                Ok((.., true, _)) => Some(Control::Shoot),
+                Ok((.., true)) => Some(Control::Interact),
+                _ => continue,
+            };
+
+            let mut entity = commands.entity(action);
+            entity.despawn_related::<Bindings>();
+            match control {
+                Some(control) => {
+                    let inputs = inputs(control);
+                    entity.insert(Bindings::spawn(SpawnIter(inputs.into_iter().map(binding))));
+                }
+                None => {
+                    let left = inputs(Control::MoveLeft);
+                    let right = inputs(Control::MoveRight);
+                    let keys = SpawnWith(move |spawner: &mut RelatedSpawner<BindingOf>| {
+                        for input in left {
+                            spawner.spawn((binding(input), Negate::all()));
+                        }
+                        for input in right {
+                            spawner.spawn(binding(input));
+                        }
+                    });
+                    if devices.is_none_or(|devices| devices.gamepad.is_some()) {
+                        entity.insert(Bindings::spawn((keys, Axial::left_stick())));
+                    } else {
+                        entity.insert(Bindings::spawn(keys));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::KeyboardHalf;
+
+    #[test]
+    fn bindings_round_trip_through_text() {
+        let text = "# comment\njump = key:Space, gamepad:South\nshoot = key:KeyJ, key:NotAKey\n";
+        let bindings = ControlBindings::from_text(text);
+
+        assert_eq!(
+            bindings.get(Control::Jump),
+            [
+                InputBinding::Key(KeyCode::Space),
+                InputBinding::Gamepad(GamepadButton::South)
+            ]
+        );
+        assert_eq!(
+            bindings.get(Control::Shoot),
+            [InputBinding::Key(KeyCode::KeyJ)]
+        );
+        assert_eq!(ControlBindings::from_text(&bindings.to_text()), bindings);
+    }
+
+    #[test]
+    fn rebinding_replaces_the_same_device_and_refuses_conflicts() {
+        let mut bindings = ControlBindings::from_text(
+            "jump = key:Space, key:KeyW, gamepad:South\nshoot = key:KeyJ",
+        );
+
+        bindings
+            .rebind(Control::Jump, InputBinding::Key(KeyCode::KeyZ))
+            .unwrap();
+        assert_eq!(
+            bindings.get(Control::Jump),
+            [
+                InputBinding::Gamepad(GamepadButton::South),
+                InputBinding::Key(KeyCode::KeyZ)
+            ]
+        );
+
+        let conflict = bindings
+            .rebind(Control::Shoot, InputBinding::Key(KeyCode::KeyZ))
+            .unwrap_err();
+        assert_eq!(conflict.existing, Control::Jump);
+        assert_eq!(
+            bindings.get(Control::Shoot),
+            [InputBinding::Key(KeyCode::KeyJ)]
+        );
+        assert!(bindings.conflicts().is_empty());
+    }
+
+    #[test]
+    fn keyboard_halves_split_the_default_keys() {
+        let left = Some(InputDevices {
+            keyboard: Some(KeyboardHalf::Left),
+            gamepad: None,
+        });
+        let right = Some(InputDevices {
+            keyboard: Some(KeyboardHalf::Right),
+            gamepad: None,
+        });
+        let bindings = ControlBindings::from_text(
+            "move_left = key:KeyA, key:ArrowLeft\njump = key:Space, key:ArrowUp, gamepad:South",
+        );
+        let usable_by = |devices, control| -> Vec<_> {
+            bindings
+                .get(control)
+                .iter()
+                .copied()
+                .filter(|&input| usable(devices, input))
+                .collect()
+        };
+
+        assert_eq!(
+            usable_by(left, Control::MoveLeft),
+            [InputBinding::Key(KeyCode::KeyA)]
+        );
+        assert_eq!(
+            usable_by(right, Control::MoveLeft),
+            [InputBinding::Key(KeyCode::ArrowLeft)]
+        );
+        assert_eq!(
+            usable_by(right, Control::Jump),
+            [InputBinding::Key(KeyCode::ArrowUp)]
+        );
+        assert_eq!(usable_by(None, Control::Jump).len(), 3);
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::{input::InputSystems, prelude::*, window::PrimaryWindow};
+use bevy_enhanced_input::prelude::*;
+
+mod bindings;
+mod prompts;
+mod touch;
+
+use crate::{
+    components::{
+        Back, Click, Confirm, MenuInput, Navigate, Pause, PlayerInput, PointerInput, Restart,
+    },
+    plugins::camera::{MainCamera, PixelPerfect},
+    states::InputMode,
+};
+
+pub use prompts::{
+    Glyph, InputPrompt, LastInputDevice, control_prompts, input_prompt, prompt_glyphs,
+};
+pub use touch::TouchControls;
+
+/// Where the cursor is, updated every frame before `Update`.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct MousePosition {
+    /// Position in the primary window in logical pixels from the top left, or `None` while the
+    /// cursor is outside the window.
+    pub screen: Option<Vec2>,
+    /// Position in the world as seen by `MainCamera`, or `None` while the cursor is outside
+    /// the camera's view (including the bars around the pixel-perfect canvas).
+    pub world: Option<Vec2>,
+    /// World position where the current [`Click`] went down, while it is held.
+    pub drag_start: Option<Vec2>,
+}
+
+impl MousePosition {
+    /// The start and current end of a drag in world space.
+    pub fn drag(&self) -> Option<(Vec2, Vec2)> {
+        Some((self.drag_start?, self.world?))
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_plugins((bindings::plugin, prompts::plugin, touch::plugin))
+        .init_resource::<MousePosition>()
+        .add_input_context::<PointerInput>()
+        .add_input_context::<MenuInput>()
+        .add_observer(activate_new_player_input)
+        .add_observer(activate_new_menu_input)
+        .add_systems(Startup, (spawn_pointer_input, spawn_menu_input))
+        .add_systems(OnEnter(InputMode::Gameplay), switch_input_contexts)
+        .add_systems(OnEnter(InputMode::Menu), switch_input_contexts)
+        .add_systems(PreUpdate, track_mouse.after(InputSystems));
+}
+
+fn spawn_pointer_input(mut commands: Commands) {
+    commands.spawn((
+        Name::new("PointerInput"),
+        PointerInput,
+        actions!(PointerInput[(Action::<Click>::new(), bindings![MouseButton::Left])]),
+    ));
+}
+
+fn spawn_menu_input(mut commands: Commands) {
+    commands.spawn((
+        Name::new("MenuInput"),
+        MenuInput,
+        // Evaluated before gameplay, so menu actions consume their inputs.
+        ContextPriority::<MenuInput>::new(1),
+        actions!(MenuInput[
+            (
+                Action::<Navigate>::new(),
+                Bindings::spawn((
+                    Cardinal::arrows(),
+                    Cardinal::wasd_keys(),
+                    Cardinal {
+                        north: Binding::from(GamepadButton::DPadUp),
+                        east: Binding::from(GamepadButton::DPadRight),
+                        south: Binding::from(GamepadButton::DPadDown),
+                        west: Binding::from(GamepadButton::DPadLeft),
+                    },
+                    Axial::left_stick(),
+                )),
+            ),
+            (
+                Action::<Confirm>::new(),
+                bindings![KeyCode::Enter, KeyCode::Space, GamepadButton::South],
+            ),
+            (
+                Action::<Back>::new(),
+                bindings![KeyCode::Backspace, GamepadButton::East],
+            ),
+            (
+                Action::<Pause>::new(),
+                bindings![KeyCode::Escape, KeyCode::KeyP, GamepadButton::Start],
+            ),
+            (
+                Action::<Restart>::new(),
+                bindings![KeyCode::KeyR, GamepadButton::Select],
+            ),
+        ]),
+    ));
+}
+
+fn player_activity(mode: InputMode) -> ContextActivity<PlayerInput> {
+    if mode == InputMode::Gameplay {
+        ContextActivity::<PlayerInput>::ACTIVE
+    } else {
+        ContextActivity::<PlayerInput>::INACTIVE
+    }
+}
+
+fn menu_activity(mode: InputMode) -> ContextActivity<MenuInput> {
+    if mode == InputMode::Menu {
+        ContextActivity::<MenuInput>::ACTIVE
+    } else {
+        ContextActivity::<MenuInput>::INACTIVE
+    }
+}
+
+/// Hands input over between gameplay and menus as the state changes.
+fn switch_input_contexts(
+    mut commands: Commands,
+    mode: Res<State<InputMode>>,
+    players: Query<Entity, With<PlayerInput>>,
+    menus: Query<Entity, With<MenuInput>>,
+) {
+    for player in players.iter() {
+        commands.entity(player).insert(player_activity(**mode));
+    }
+    for menu in menus.iter() {
+        commands.entity(menu).insert(menu_activity(**mode));
+    }
+}
+
+fn activate_new_player_input(
+    add: On<Add, PlayerInput>,
+    mut commands: Commands,
+    mode: Option<Res<State<InputMode>>>,
+) {
+    if let Some(mode) = mode {
+        commands.entity(add.entity).insert(player_activity(**mode));
+    }
+}
+
+fn activate_new_menu_input(
+    add: On<Add, MenuInput>,
+    mut commands: Commands,
+    mode: Option<Res<State<InputMode>>>,
+) {
+    if let Some(mode) = mode {
+        commands.entity(add.entity).insert(menu_activity(**mode));
+    }
+}
+
+fn track_mouse(
+    mut mouse: ResMut<MousePosition>,
+    settings: Res<PixelPerfect>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    click: Query<&ActionState, With<Action<Click>>>,
+) {
+    let window = windows.single().ok();
+    let screen = window.and_then(Window::cursor_position);
+    let world = screen.zip(window).and_then(|(screen, window)| {
+        let (camera, transform) = cameras.single().ok()?;
+        let viewport = settings.window_to_viewport(screen, window.size());
+        if !camera
+            .logical_viewport_rect()
+            .is_some_and(|rect| rect.contains(viewport))
+        {
+            return None;
+        }
+        camera.viewport_to_world_2d(transform, viewport).ok()
+    });
+
+    let held = click.iter().any(|state| *state == ActionState::Fired);
+    let drag_start = if held {
+        mouse.drag_start.or(world)
+    } else {
+        None
+    };
+
+    mouse.set_if_neq(MousePosition {
+        screen,
+        world,
+        drag_start,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{ecs::system::RunSystemOnce, input::InputPlugin};
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            EnhancedInputPlugin,
+            plugin,
+        ))
+        .init_resource::<PixelPerfect>();
+        app
+    }
+
+    #[test]
+    fn plugin_registers_resources() {
+        let mut app = setup();
+
+        // Check if the plugins are registered
+        assert!(app.world().contains_resource::<MousePosition>());
+    }
+
+    #[test]
+    fn cursor_outside_the_window_clears_positions() {
+        let mut app = setup();
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.insert_resource(MousePosition {
+            screen: Some(Vec2::ONE),
+            world: Some(Vec2::ONE),
+            drag_start: Some(Vec2::ZERO),
+        });
+
+        app.world_mut().run_system_once(track_mouse).unwrap();
+
+        assert_eq!(
+            *app.world().resource::<MousePosition>(),
+            MousePosition::default()
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Input prompts: a label followed by glyphs for whatever is bound to some controls, drawn as
+//! keycaps or gamepad buttons depending on which device was used last. Spawn
+//! [`input_prompt`] anywhere in the UI and it keeps itself up to date as bindings change.
+
+use bevy::{input::InputSystems, prelude::*, text::FontSmoothing};
+
+use crate::{
+    plugins::fonts::FontAssets,
+    resources::{Control, ControlBindings, InputBinding},
+};
+
+const GLYPH_SIZE: f32 = 32.0;
+const GLYPH_FONT_SIZE: f32 = 24.0;
+const PROMPT_FONT_SIZE: f32 = 28.0;
+const GLYPH_TEXT_COLOR: Color = Color::WHITE;
+const KEYCAP_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const KEYCAP_BORDER_COLOR: Color = Color::srgb(0.75, 0.75, 0.8);
+const PAD_BUTTON_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+/// How far a stick has to be pushed to count as using the gamepad.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The kind of device the last input came from, which decides the glyphs prompts show.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LastInputDevice {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
+/// Something to press, as shown in a prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Glyph {
+    Key(KeyCode),
+    Button(GamepadButton),
+    /// Movement is always on the left stick, whatever the bindings say.
+    LeftStick,
+}
+
+impl Glyph {
+    /// The short text printed on the glyph.
+    pub fn label(self) -> String {
+        use GamepadButton::*;
+        match self {
+            Self::Key(key) => key_label(key),
+            Self::Button(button) => match button {
+                South => "A",
+                East => "B",
+                West => "X",
+                North => "Y",
+                LeftTrigger => "LB",
+                RightTrigger => "RB",
+                LeftTrigger2 => "LT",
+                RightTrigger2 => "RT",
+                LeftThumb => "L3",
+                RightThumb => "R3",
+                Select => "Back",
+                Start => "Start",
+                Mode => "Home",
+                DPadUp => "D^",
+                DPadDown => "Dv",
+                DPadLeft => "D<",
+                DPadRight => "D>",
+                _ => "?",
+            }
+            .into(),
+            Self::LeftStick => "LS".into(),
+        }
+    }
+
+    /// Face buttons take the colors printed on them.
+    fn color(self) -> Color {
+        match self {
+            Self::Button(GamepadButton::South) => Color::srgb(0.25, 0.6, 0.25),
+            Self::Button(GamepadButton::East) => Color::srgb(0.7, 0.25, 0.25),
+            Self::Button(GamepadButton::West) => Color::srgb(0.25, 0.4, 0.75),
+            Self::Button(GamepadButton::North) => Color::srgb(0.75, 0.65, 0.2),
+            Self::Key(_) => KEYCAP_COLOR,
+            _ => PAD_BUTTON_COLOR,
+        }
+    }
+}
+
+fn key_label(key: KeyCode) -> String {
+    use KeyCode::*;
+    let label = match key {
+        ArrowUp => "^",
+        ArrowDown => "v",
+        ArrowLeft => "<",
+        ArrowRight => ">",
+        Space => "Space",
+        Enter => "Enter",
+        Escape => "Esc",
+        Backspace => "Bksp",
+        Tab => "Tab",
+        ShiftLeft | ShiftRight => "Shift",
+        ControlLeft | ControlRight => "Ctrl",
+        AltLeft | AltRight => "Alt",
+        _ => {
+            // `KeyA` and `Digit1` print as `A` and `1`.
+            let name = format!("{key:?}");
+            return name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .unwrap_or(&name)
+                .to_owned();
+        }
+    };
+    label.into()
+}
+
+/// The glyphs for `controls` on `device`, in binding order without repeats. Movement prompts on
+/// a gamepad lead with the left stick.
+pub fn prompt_glyphs(
+    bindings: &ControlBindings,
+    controls: &[Control],
+    device: LastInputDevice,
+) -> Vec<Glyph> {
+    let mut glyphs = Vec::new();
+    if device == LastInputDevice::Gamepad
+        && controls
+            .iter()
+            .any(|control| matches!(control, Control::MoveLeft | Control::MoveRight))
+    {
+        glyphs.push(Glyph::LeftStick);
+    }
+    for &control in controls {
+        for &binding in bindings.get(control) {
+            let glyph = match (binding, device) {
+                (InputBinding::Key(key), LastInputDevice::Keyboard) => Glyph::Key(key),
+                (InputBinding::Gamepad(button), LastInputDevice::Gamepad) => Glyph::Button(button),
+                _ => continue,
+            };
+            if !glyphs.contains(&glyph) {
+                glyphs.push(glyph);
+            }
+        }
+    }
+    glyphs
+}
+
+/// A label and the glyphs of its controls. Its children are rebuilt whenever the bindings or the
+/// last-used device change.
+#[derive(Component, Clone, Debug)]
+pub struct InputPrompt {
+    pub label: String,
+    pub controls: Vec<Control>,
+}
+
+/// A prompt laid out as a row, ready to be spawned in any UI.
+pub fn input_prompt(label: impl Into<String>, controls: impl Into<Vec<Control>>) -> impl Bundle {
+    (
+        InputPrompt {
+            label: label.into(),
+            controls: controls.into(),
+        },
+        Node {
+            column_gap: Val::Px(6.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+    )
+}
+
+/// A prompt for every player control, for the HUD and menus.
+pub fn control_prompts() -> impl Bundle {
+    (
+        Node {
+            column_gap: Val::Px(32.0),
+            ..default()
+        },
+        children![
+            input_prompt("Move", [Control::MoveLeft, Control::MoveRight]),
+            input_prompt("Jump", [Control::Jump]),
+            input_prompt("Shoot", [Control::Shoot]),
+            input_prompt("Interact", [Control::Interact]),
+        ],
+    )
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LastInputDevice>()
+        .add_systems(PreUpdate, track_last_device.after(InputSystems))
+        .add_systems(
+            Update,
+            rebuild_prompts.run_if(any_with_component::<InputPrompt>),
+        );
+}
+
+fn track_last_device(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut last: ResMut<LastInputDevice>,
+) {
+    if keys.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some() {
+        last.set_if_neq(LastInputDevice::Keyboard);
+    } else if gamepads.iter().any(|gamepad| {
+        gamepad.get_just_pressed().next().is_some()
+            || gamepad.left_stick().length() > STICK_THRESHOLD
+    }) {
+        last.set_if_neq(LastInputDevice::Gamepad);
+    }
+}
+
+fn glyph_text(fonts: &FontAssets, text: String, font_size: f32) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font: fonts.default.clone(),
+            font_size,
+            font_smoothing: FontSmoothing::None,
+            ..default()
+        },
+        TextColor(GLYPH_TEXT_COLOR),
+    )
+}
+
+/// Keys are drawn as outlined keycaps and gamepad inputs as round buttons.
+fn glyph_node(fonts: &FontAssets, glyph: Glyph) -> impl Bundle {
+    let is_key = matches!(glyph, Glyph::Key(_));
+    (
+        Node {
+            min_width: Val::Px(GLYPH_SIZE),
+            height: Val::Px(GLYPH_SIZE),
+            padding: UiRect::horizontal(Val::Px(6.0)),
+            border: UiRect::all(Val::Px(if is_key { 2.0 } else { 0.0 })),
+            border_radius: if is_key {
+                BorderRadius::all(Val::Px(6.0))
+            } else {
+                BorderRadius::MAX
+            },
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(glyph.color()),
+        BorderColor::all(KEYCAP_BORDER_COLOR),
+        children![glyph_text(fonts, glyph.label(), GLYPH_FONT_SIZE)],
+    )
+}
+
+fn rebuild_prompts(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    bindings: Res<ControlBindings>,
+    device: Res<LastInputDevice>,
+    prompts: Query<(Entity, Ref<InputPrompt>)>,
+) {
+    for (entity, prompt) in prompts.iter() {
+        if !bindings.is_changed() && !device.is_changed() && !prompt.is_changed() {
+            continue;
+        }
+        let glyphs = prompt_glyphs(&bindings, &prompt.controls, *device);
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .with_children(|row| {
+                row.spawn(glyph_text(&fonts, prompt.label.clone(), PROMPT_FONT_SIZE));
+                if glyphs.is_empty() {
+                    row.spawn(glyph_text(&fonts, "Unbound".into(), GLYPH_FONT_SIZE));
+                }
+                for glyph in glyphs {
+                    row.spawn(glyph_node(&fonts, glyph));
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_show_the_bindings_of_the_last_device() {
+        let bindings = ControlBindings::from_text(
+            "move_left = key:KeyA, key:ArrowLeft\n\
+             move_right = key:KeyD, key:ArrowRight\n\
+             jump = key:Space, key:KeyW, gamepad:South",
+        );
+        let movement = [Control::MoveLeft, Control::MoveRight];
+
+        assert_eq!(
+            prompt_glyphs(&bindings, &movement, LastInputDevice::Keyboard)
+                .into_iter()
+                .map(Glyph::label)
+                .collect::<Vec<_>>(),
+            ["A", "<", "D", ">"]
+        );
+        assert_eq!(
+            prompt_glyphs(&bindings, &movement, LastInputDevice::Gamepad),
+            [Glyph::LeftStick]
+        );
+        assert_eq!(
+            prompt_glyphs(&bindings, &[Control::Jump], LastInputDevice::Gamepad),
+            [Glyph::Button(GamepadButton::South)]
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! On-screen touch controls: a virtual joystick and a jump button for the first player. They
+//! show up once the screen is touched and hide again when a key or gamepad button is pressed.
+//! Each control holds on to the finger that started on it, so both work at once.
+
+use bevy::{prelude::*, text::FontSmoothing};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{Jump, Move, Player, PlayerInput, PlayerNumber},
+    plugins::fonts::FontAssets,
+    resources::InputReplay,
+    states::{GameState, InputMode},
+};
+
+const JOYSTICK_SIZE: f32 = 180.0;
+const KNOB_SIZE: f32 = 72.0;
+const JUMP_BUTTON_SIZE: f32 = 120.0;
+const CONTROL_MARGIN: f32 = 48.0;
+/// How far from the center the joystick has to be pushed before it moves the player, as a
+/// fraction of its radius.
+const JOYSTICK_DEADZONE: f32 = 0.15;
+const CONTROL_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const KNOB_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+const PRESSED_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.45);
+
+/// The state of the touch controls, updated every frame.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TouchControls {
+    /// Whether the controls are on screen. Set by the last kind of input used.
+    pub visible: bool,
+    /// The touch holding the joystick.
+    pub joystick: Option<u64>,
+    /// The touch holding the jump button.
+    pub jump: Option<u64>,
+    /// The joystick's value, with up positive like the `Move` action.
+    pub movement: Vec2,
+}
+
+#[derive(Component)]
+struct TouchJoystick;
+
+#[derive(Component)]
+struct TouchKnob;
+
+#[derive(Component)]
+struct TouchJumpButton;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TouchControls>()
+        .add_systems(OnEnter(GameState::Playing), spawn_touch_controls)
+        .add_systems(
+            Update,
+            (detect_touch_screen, track_touches, update_touch_controls)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            FixedFirst,
+            feed_touch_actions.run_if(in_state(GameState::Playing)),
+        );
+}
+
+fn spawn_touch_controls(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands.spawn((
+        Name::new("TouchJoystick"),
+        TouchJoystick,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(CONTROL_MARGIN),
+            bottom: Val::Px(CONTROL_MARGIN),
+            width: Val::Px(JOYSTICK_SIZE),
+            height: Val::Px(JOYSTICK_SIZE),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        BackgroundColor(CONTROL_COLOR),
+        Visibility::Hidden,
+        DespawnOnExit(GameState::Playing),
+        children![(
+            TouchKnob,
+            Node {
+                width: Val::Px(KNOB_SIZE),
+                height: Val::Px(KNOB_SIZE),
+                border_radius: BorderRadius::MAX,
+                ..default()
+            },
+            BackgroundColor(KNOB_COLOR),
+            UiTransform::default(),
+        )],
+    ));
+    commands.spawn((
+        Name::new("TouchJumpButton"),
+        TouchJumpButton,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(CONTROL_MARGIN),
+            bottom: Val::Px(CONTROL_MARGIN),
+            width: Val::Px(JUMP_BUTTON_SIZE),
+            height: Val::Px(JUMP_BUTTON_SIZE),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        BackgroundColor(CONTROL_COLOR),
+        Visibility::Hidden,
+        DespawnOnExit(GameState::Playing),
+        children![(
+            Text::new("JUMP"),
+            TextFont {
+                font: fonts.default.clone(),
+                font_size: 28.0,
+                font_smoothing: FontSmoothing::None,
+                ..default()
+            },
+            TextColor(KNOB_COLOR),
+        )],
+    ));
+}
+
+/// Shows the controls on the first touch and hides them once a key or gamepad button is used.
+fn detect_touch_screen(
+    touches: Res<Touches>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut controls: ResMut<TouchControls>,
+) {
+    if touches.any_just_pressed() {
+        controls.visible = true;
+    } else if keys.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+    {
+        controls.visible = false;
+    }
+}
+
+/// A node's area in logical pixels, the space touches are reported in.
+fn node_rect(node: &ComputedNode, transform: &UiGlobalTransform) -> Rect {
+    let scale = node.inverse_scale_factor();
+    Rect::from_center_size(transform.translation * scale, node.size() * scale)
+}
+
+/// The joystick's value for a touch `offset` from its center: up is positive, the length is
+/// capped at one and small pushes count as none.
+fn joystick_value(offset: Vec2, radius: f32) -> Vec2 {
+    let value = (Vec2::new(offset.x, -offset.y) / radius).clamp_length_max(1.0);
+    if value.length() < JOYSTICK_DEADZONE {
+        Vec2::ZERO
+    } else {
+        value
+    }
+}
+
+fn track_touches(
+    touches: Res<Touches>,
+    mut controls: ResMut<TouchControls>,
+    joysticks: Query<(&ComputedNode, &UiGlobalTransform), With<TouchJoystick>>,
+    buttons: Query<(&ComputedNode, &UiGlobalTransform), With<TouchJumpButton>>,
+) {
+    let controls = &mut *controls;
+    // Let go of fingers that have lifted.
+    for claimed in [&mut controls.joystick, &mut controls.jump] {
+        if claimed.is_some_and(|id| touches.get_pressed(id).is_none()) {
+            *claimed = None;
+        }
+    }
+
+    let joystick = joysticks
+        .iter()
+        .next()
+        .map(|(node, transform)| node_rect(node, transform));
+    let button = buttons
+        .iter()
+        .next()
+        .map(|(node, transform)| node_rect(node, transform));
+    for touch in touches.iter_just_pressed() {
+        let on = |rect: Option<Rect>| rect.is_some_and(|rect| rect.contains(touch.position()));
+        if controls.joystick.is_none() && on(joystick) {
+            controls.joystick = Some(touch.id());
+        } else if controls.jump.is_none() && on(button) {
+            controls.jump = Some(touch.id());
+        }
+    }
+
+    controls.movement = match (controls.joystick, joystick) {
+        (Some(id), Some(rect)) => touches.get_pressed(id).map_or(Vec2::ZERO, |touch| {
+            joystick_value(touch.position() - rect.center(), rect.half_size().x)
+        }),
+        _ => Vec2::ZERO,
+    };
+}
+
+fn update_touch_controls(
+    controls: Res<TouchControls>,
+    mode: Res<State<InputMode>>,
+    mut visibilities: Query<
+        (&mut Visibility, Has<TouchJumpButton>, &mut BackgroundColor),
+        Or<(With<TouchJoystick>, With<TouchJumpButton>)>,
+    >,
+    mut knobs: Query<&mut UiTransform, With<TouchKnob>>,
+) {
+    // Menus take touches as clicks, so the controls step aside.
+    let visible = controls.visible && *mode.get() == InputMode::Gameplay;
+    for (mut visibility, is_button, mut color) in visibilities.iter_mut() {
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        let pressed = if is_button {
+            controls.jump.is_some()
+        } else {
+            controls.joystick.is_some()
+        };
+        color.set_if_neq(BackgroundColor(if pressed {
+            PRESSED_COLOR
+        } else {
+            CONTROL_COLOR
+        }));
+    }
+    let reach = (JOYSTICK_SIZE - KNOB_SIZE) * 0.5;
+    for mut knob in knobs.iter_mut() {
+        let offset = controls.movement * reach;
+        knob.translation = Val2::px(offset.x, -offset.y);
+    }
+}
+
+/// Feeds the held controls into the first player's `Move` and `Jump` actions for this tick.
+/// Actions without a finger on them are left to the player's other bindings.
+fn feed_touch_actions(
+    mut commands: Commands,
+    controls: Res<TouchControls>,
+    replay: Option<Res<InputReplay>>,
+    players: Query<(&PlayerNumber, &Actions<PlayerInput>), With<Player>>,
+    actions: Query<(Has<Action<Move>>, Has<Action<Jump>>)>,
+) {
+    // A replay drives the players on its own.
+    if (controls.joystick.is_none() && controls.jump.is_none())
+        || replay.is_some_and(|replay| replay.is_replaying())
+    {
+        return;
+    }
+    let Some((_, context)) = players.iter().min_by_key(|(number, _)| **number) else {
+        return;
+    };
+    for action in context.iter() {
+        match actions.get(action) {
+            Ok((true, _)) if controls.joystick.is_some() => {
+                let state = if controls.movement == Vec2::ZERO {
+                    ActionState::None
+                } else {
+                    ActionState::Fired
+                };
+                commands
+                    .entity(action)
+                    .insert(ActionMock::once(state, controls.movement));
+            }
+            Ok((_, true)) if controls.jump.is_some() => {
+                commands
+                    .entity(action)
+                    .insert(ActionMock::once(ActionState::Fired, true));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joystick_points_up_and_caps_at_full_tilt() {
+        assert_eq!(joystick_value(Vec2::new(0.0, -45.0), 90.0), Vec2::Y * 0.5);
+        assert_eq!(joystick_value(Vec2::new(300.0, 0.0), 90.0), Vec2::X);
+        assert_eq!(joystick_value(Vec2::new(5.0, 5.0), 90.0), Vec2::ZERO);
+    }
+}
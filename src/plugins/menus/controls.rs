@@ -0,0 +1,341 @@
+//! Controls screen. Lists each control's bindings; clicking one listens for the next key or
+//! gamepad button and rebinds the control to it.
+
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    events::{BindingConflict, RebindControl, ResetBindings},
+    plugins::fonts::FontAssets,
+    resources::{Control, ControlBindings, InputBinding},
+};
+
+use super::{
+    BUTTON_COLOR, MenuAction, MenuButton, menu_button, menu_root, menu_text,
+    navigation::read_menu_input,
+};
+
+#[derive(Component)]
+pub(super) struct ControlsScreen;
+
+/// Shows the bindings of a control on its rebind button.
+#[derive(Component)]
+struct BindingText(Control);
+
+#[derive(Component)]
+struct ControlsMessage;
+
+/// The control waiting for an input, if any.
+#[derive(Resource, Default)]
+pub(super) struct Listening(pub Option<Control>);
+
+const HINT: &str = "Click a control to rebind it";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Listening>()
+        .add_observer(handle_controls_action)
+        .add_observer(show_conflict)
+        .add_systems(
+            Update,
+            (capture_binding, update_binding_text)
+                .chain()
+                .after(read_menu_input)
+                .run_if(any_with_component::<ControlsScreen>),
+        );
+}
+
+fn spawn_controls_screen(commands: &mut Commands, fonts: &FontAssets) {
+    commands
+        .spawn((
+            Name::new("ControlsScreen"),
+            ControlsScreen,
+            menu_root(),
+            // Above the menu that opened it, and keep clicks from reaching that menu.
+            GlobalZIndex(20),
+            FocusPolicy::Block,
+        ))
+        .with_children(|screen| {
+            screen.spawn(menu_text(fonts, "CONTROLS", 72.0));
+            for control in Control::ALL {
+                screen.spawn((
+                    Node {
+                        column_gap: Val::Px(16.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![
+                        (
+                            menu_text(fonts, control.label(), 28.0),
+                            Node {
+                                width: Val::Px(200.0),
+                                ..default()
+                            },
+                        ),
+                        (
+                            Button,
+                            MenuButton(MenuAction::Rebind(control)),
+                            Node {
+                                width: Val::Px(520.0),
+                                padding: UiRect::all(Val::Px(8.0)),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor(BUTTON_COLOR),
+                            children![(menu_text(fonts, "", 24.0), BindingText(control))],
+                        ),
+                    ],
+                ));
+            }
+            screen.spawn((menu_text(fonts, HINT, 24.0), ControlsMessage));
+            screen.spawn(menu_button(fonts, "Reset", MenuAction::ResetBindings));
+            screen.spawn(menu_button(fonts, "Back", MenuAction::Back));
+        });
+}
+
+fn handle_controls_action(
+    action: On<MenuAction>,
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    mut listening: ResMut<Listening>,
+    screens: Query<Entity, With<ControlsScreen>>,
+    mut messages: Query<&mut Text, With<ControlsMessage>>,
+) {
+    match *action {
+        MenuAction::Controls if screens.is_empty() => {
+            spawn_controls_screen(&mut commands, &fonts);
+        }
+        MenuAction::Back => {
+            listening.0 = None;
+            for screen in screens.iter() {
+                commands.entity(screen).despawn();
+            }
+        }
+        MenuAction::Rebind(control) => {
+            listening.0 = Some(control);
+            for mut text in messages.iter_mut() {
+                text.0 = format!(
+                    "Press a key or button for {} (Esc cancels)",
+                    control.label()
+                );
+            }
+        }
+        MenuAction::ResetBindings => {
+            listening.0 = None;
+            commands.trigger(ResetBindings);
+            for mut text in messages.iter_mut() {
+                text.0 = HINT.into();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Takes the next key or gamepad button pressed while listening. Mouse buttons are left out,
+/// since the click that started listening would be taken straight away. Runs after menu input,
+/// so a confirm press that starts listening is seen here in the same frame and skipped.
+fn capture_binding(
+    mut commands: Commands,
+    mut listening: ResMut<Listening>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut messages: Query<&mut Text, With<ControlsMessage>>,
+) {
+    let Some(control) = listening.0 else {
+        return;
+    };
+    if listening.is_changed() {
+        return;
+    }
+    // Menu input already saw this press while listening and ignored it; consume it so nothing
+    // later in the frame takes it for backing out of the screen.
+    if keys.clear_just_pressed(KeyCode::Escape) {
+        listening.0 = None;
+        for mut text in messages.iter_mut() {
+            text.0 = HINT.into();
+        }
+        return;
+    }
+    let pressed = keys
+        .get_just_pressed()
+        .next()
+        .map(|&key| InputBinding::Key(key))
+        .or_else(|| {
+            gamepads
+                .iter()
+                .find_map(|gamepad| gamepad.get_just_pressed().next())
+                .map(|&button| InputBinding::Gamepad(button))
+        });
+    let Some(binding) = pressed else {
+        return;
+    };
+
+    listening.0 = None;
+    // Replaced by `show_conflict` if the input is already taken.
+    for mut text in messages.iter_mut() {
+        text.0 = format!("{} bound to {binding}", control.label());
+    }
+    commands.trigger(RebindControl { control, binding });
+}
+
+fn show_conflict(
+    conflict: On<BindingConflict>,
+    mut messages: Query<&mut Text, With<ControlsMessage>>,
+) {
+    for mut text in messages.iter_mut() {
+        text.0 = conflict.to_string();
+    }
+}
+
+fn update_binding_text(
+    bindings: Res<ControlBindings>,
+    listening: Res<Listening>,
+    mut texts: Query<(Ref<BindingText>, &mut Text)>,
+) {
+    for (binding_text, mut text) in texts.iter_mut() {
+        if !bindings.is_changed() && !listening.is_changed() && !binding_text.is_added() {
+            continue;
+        }
+        let control = binding_text.0;
+        text.0 = if listening.0 == Some(control) {
+            "...".into()
+        } else {
+            let inputs: Vec<_> = bindings
+                .get(control)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            if inputs.is_empty() {
+                "Unbound".into()
+            } else {
+                inputs.join(", ")
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_enhanced_input::prelude::*;
+
+    use super::*;
+    use crate::{
+        components::{Confirm, MenuInput, Pause},
+        plugins::menus::{MenuFocus, navigation},
+    };
+
+    /// Rebinds requested so far.
+    #[derive(Resource, Default)]
+    struct Rebinds(Vec<RebindControl>);
+
+    /// Keys and menu actions are set by hand, with no input plugins, so each frame sees
+    /// exactly the presses the test makes.
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin, navigation::plugin))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Rebinds>()
+            .insert_resource(FontAssets {
+                default: Handle::default(),
+            })
+            .add_observer(|rebind: On<RebindControl>, mut rebinds: ResMut<Rebinds>| {
+                rebinds.0.push(*rebind);
+            });
+        app.world_mut().spawn((
+            MenuInput,
+            actions!(MenuInput[
+                (Action::<Confirm>::new(), ActionState::None),
+                (Action::<Pause>::new(), ActionState::None),
+            ]),
+        ));
+        app.world_mut().trigger(MenuAction::Controls);
+        app
+    }
+
+    fn set_action<A: InputAction>(app: &mut App, fired: bool) {
+        let mut states = app
+            .world_mut()
+            .query_filtered::<&mut ActionState, With<Action<A>>>();
+        for mut state in states.iter_mut(app.world_mut()) {
+            *state = if fired {
+                ActionState::Fired
+            } else {
+                ActionState::None
+            };
+        }
+    }
+
+    /// Runs a frame with only `key` pressed.
+    fn press(app: &mut App, key: Option<KeyCode>) {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.reset_all();
+        if let Some(key) = key {
+            keys.press(key);
+        }
+        app.update();
+    }
+
+    fn listening(app: &App) -> Option<Control> {
+        app.world().resource::<Listening>().0
+    }
+
+    fn rebinds(app: &App) -> &[RebindControl] {
+        &app.world().resource::<Rebinds>().0
+    }
+
+    #[test]
+    fn the_confirm_press_that_starts_listening_is_not_captured() {
+        let mut app = setup();
+        let jump = app
+            .world_mut()
+            .query::<(Entity, &MenuButton)>()
+            .iter(app.world())
+            .find(|(_, button)| button.0 == MenuAction::Rebind(Control::Jump))
+            .map(|(entity, _)| entity)
+            .unwrap();
+        app.world_mut().entity_mut(jump).insert(MenuFocus);
+
+        set_action::<Confirm>(&mut app, true);
+        press(&mut app, Some(KeyCode::Enter));
+        assert_eq!(listening(&app), Some(Control::Jump));
+        assert!(rebinds(&app).is_empty());
+
+        set_action::<Confirm>(&mut app, false);
+        press(&mut app, None);
+        press(&mut app, Some(KeyCode::KeyK));
+        assert_eq!(listening(&app), None);
+        assert_eq!(rebinds(&app).len(), 1);
+        assert_eq!(rebinds(&app)[0].control, Control::Jump);
+        assert_eq!(rebinds(&app)[0].binding, InputBinding::Key(KeyCode::KeyK));
+    }
+
+    #[test]
+    fn escape_cancels_listening_without_closing_the_screen() {
+        let mut app = setup();
+        app.insert_resource(Listening(Some(Control::Jump)));
+        press(&mut app, None);
+        let screen_open = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<(), With<ControlsScreen>>()
+                .iter(app.world())
+                .count()
+                == 1
+        };
+
+        // Esc is also bound to pause, which backs out of the screen.
+        set_action::<Pause>(&mut app, true);
+        press(&mut app, Some(KeyCode::Escape));
+        assert_eq!(listening(&app), None);
+        assert!(screen_open(&mut app));
+
+        // Still held on the next frame, so it isn't a new press either.
+        app.update();
+        assert!(screen_open(&mut app));
+        assert!(rebinds(&app).is_empty());
+
+        // A fresh press backs out as usual.
+        set_action::<Pause>(&mut app, false);
+        press(&mut app, None);
+        set_action::<Pause>(&mut app, true);
+        press(&mut app, Some(KeyCode::Escape));
+        assert!(!screen_open(&mut app));
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::{
+    plugins::fonts::FontAssets,
+    resources::{CoinState, RunStats},
+    states::GameState,
+    utils::{format_coins, format_run_time},
+};
+
+use super::{MenuAction, menu_button, menu_root, menu_text};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen);
+}
+
+fn spawn_game_over_screen(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    coin_state: Res<CoinState>,
+    stats: Res<RunStats>,
+) {
+    commands.spawn((
+        Name::new("GameOverScreen"),
+        menu_root(),
+        DespawnOnExit(GameState::GameOver),
+        children![
+            menu_text(&fonts, "GAME OVER", 72.0),
+            menu_text(&fonts, format_coins(&coin_state), 36.0),
+            menu_text(
+                &fonts,
+                format!("Time: {}", format_run_time(stats.elapsed_secs)),
+                36.0
+            ),
+            menu_text(&fonts, format!("Deaths: {}", stats.deaths), 36.0),
+            menu_button(&fonts, "Retry", MenuAction::Retry),
+            menu_button(&fonts, "Controls", MenuAction::Controls),
+            menu_button(&fonts, "Quit", MenuAction::Quit),
+        ],
+    ));
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{
+    plugins::fonts::FontAssets,
+    resources::{HighScores, LevelResult},
+    states::GameState,
+    utils::format_run_time,
+};
+
+use super::{MenuAction, menu_button, menu_root, menu_text};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GameState::LevelComplete),
+        spawn_level_complete_screen,
+    );
+}
+
+fn spawn_level_complete_screen(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    result: Res<LevelResult>,
+    high_scores: Res<HighScores>,
+) {
+    commands
+        .spawn((
+            Name::new("LevelCompleteScreen"),
+            menu_root(),
+            DespawnOnExit(GameState::LevelComplete),
+        ))
+        .with_children(|screen| {
+            screen.spawn(menu_text(&fonts, "LEVEL COMPLETE", 72.0));
+            screen.spawn(menu_text(
+                &fonts,
+                format!("Pickups: {}", result.pickups),
+                36.0,
+            ));
+            for bonus in &result.bonuses {
+                screen.spawn(menu_text(
+                    &fonts,
+                    format!("{} bonus: {}", bonus.label, bonus.points),
+                    36.0,
+                ));
+            }
+            screen.spawn(menu_text(&fonts, format!("Total: {}", result.total), 48.0));
+
+            screen.spawn(menu_text(&fonts, "High Scores", 36.0));
+            for (rank, entry) in high_scores.get(&result.level).iter().enumerate() {
+                // Mark the run that just finished.
+                let marker = if result.rank == Some(rank) { ">" } else { " " };
+                screen.spawn(menu_text(
+                    &fonts,
+                    format!(
+                        "{marker}{}. {:>7}  {}",
+                        rank + 1,
+                        entry.points,
+                        format_run_time(entry.secs)
+                    ),
+                    32.0,
+                ));
+            }
+
+            screen.spawn(menu_button(&fonts, "Play Again", MenuAction::Retry));
+            screen.spawn(menu_button(&fonts, "Controls", MenuAction::Controls));
+            screen.spawn(menu_button(&fonts, "Quit", MenuAction::Quit));
+        });
+}
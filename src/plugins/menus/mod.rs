@@ -0,0 +1,134 @@
+//! Menu screens and the buttons they share.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{plugins::fonts::FontAssets, resources::Control};
+
+mod controls;
+mod game_over;
+mod level_complete;
+mod navigation;
+mod pause;
+mod reconnect;
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.75);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.3, 0.3, 0.38);
+const BUTTON_PRESSED_COLOR: Color = Color::srgb(0.4, 0.4, 0.5);
+
+/// What a menu button does. Triggered as an event when the button is pressed, so each screen
+/// or gameplay module observes only the actions it cares about.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Retry,
+    Quit,
+    /// Closes the pause menu.
+    Resume,
+    /// Opens the controls screen.
+    Controls,
+    /// Closes the controls screen.
+    Back,
+    /// Listens for a new input for the control.
+    Rebind(Control),
+    ResetBindings,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MenuButton(pub MenuAction);
+
+/// The button that keyboard and gamepad navigation is on.
+#[derive(Component, Debug)]
+pub struct MenuFocus;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_plugins((
+        controls::plugin,
+        game_over::plugin,
+        level_complete::plugin,
+        navigation::plugin,
+        pause::plugin,
+        reconnect::plugin,
+    ))
+    .add_observer(quit)
+    .add_systems(Update, (press_menu_buttons, color_menu_buttons));
+}
+
+/// Full-screen dimmed column that menu screens are built in.
+pub fn menu_root() -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(16.0),
+            ..default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+        // Draw above the HUD.
+        GlobalZIndex(10),
+    )
+}
+
+pub fn menu_text(fonts: &FontAssets, text: impl Into<String>, font_size: f32) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font: fonts.default.clone(),
+            font_size,
+            font_smoothing: FontSmoothing::None,
+            ..default()
+        },
+        TextColor(TEXT_COLOR),
+    )
+}
+
+pub fn menu_button(fonts: &FontAssets, label: &str, action: MenuAction) -> impl Bundle {
+    (
+        Button,
+        MenuButton(action),
+        Node {
+            width: Val::Px(240.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR),
+        children![menu_text(fonts, label, 36.0)],
+    )
+}
+
+fn press_menu_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.trigger(button.0);
+        }
+    }
+}
+
+/// Highlights buttons under the pointer and the one focused by keyboard or gamepad.
+fn color_menu_buttons(
+    mut buttons: Query<(&Interaction, Has<MenuFocus>, &mut BackgroundColor), With<MenuButton>>,
+) {
+    for (interaction, focused, mut background) in buttons.iter_mut() {
+        let color = match interaction {
+            Interaction::Pressed => BUTTON_PRESSED_COLOR,
+            Interaction::Hovered => BUTTON_HOVERED_COLOR,
+            Interaction::None if focused => BUTTON_HOVERED_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        };
+        background.set_if_neq(BackgroundColor(color));
+    }
+}
+
+fn quit(action: On<MenuAction>, mut exit: MessageWriter<AppExit>) {
+    if *action == MenuAction::Quit {
+        exit.write(AppExit::Success);
+    }
+}
@@ -0,0 +1,225 @@
+//! Keyboard and gamepad menu navigation through the `MenuInput` context: moving the focus
+//! between buttons, pressing the focused one, backing out, pausing and restarting.
+
+use bevy::{
+    ecs::{query::QueryFilter, system::SystemParam},
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    components::{Back, Confirm, MenuInput, Navigate, Pause, PlayerInput, Restart},
+    states::{InputMode, PauseState},
+};
+
+use super::{
+    MenuAction, MenuButton, MenuFocus,
+    controls::{ControlsScreen, Listening},
+};
+
+/// How far the stick has to be pushed to count as a step.
+const NAVIGATE_THRESHOLD: f32 = 0.5;
+
+/// Which menu actions were held last frame, so each press acts once.
+#[derive(Default)]
+struct HeldActions {
+    navigate: bool,
+    confirm: bool,
+    back: bool,
+    pause: bool,
+    restart: bool,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, (read_menu_input, focus_first_button).chain());
+}
+
+fn pressed<F: QueryFilter>(states: &Query<&ActionState, F>) -> bool {
+    states.iter().any(|state| *state == ActionState::Fired)
+}
+
+/// Pause and restart are bound in both the gameplay and the menu context. Gameplay actions only
+/// update on fixed ticks, which stop while paused, so only the live context's are read; a stale
+/// press would otherwise stay held and keep the game from unpausing.
+#[derive(SystemParam)]
+struct PauseActions<'w, 's> {
+    mode: Option<Res<'w, State<InputMode>>>,
+    players: Query<'w, 's, &'static Actions<PlayerInput>>,
+    menus: Query<'w, 's, &'static Actions<MenuInput>>,
+    pause: Query<'w, 's, &'static ActionState, With<Action<Pause>>>,
+    restart: Query<'w, 's, &'static ActionState, With<Action<Restart>>>,
+}
+
+impl PauseActions<'_, '_> {
+    fn live_actions(&self) -> Vec<Entity> {
+        let gameplay = self
+            .mode
+            .as_ref()
+            .is_some_and(|mode| *mode.get() == InputMode::Gameplay);
+        if gameplay {
+            self.players
+                .iter()
+                .flat_map(|actions| actions.iter())
+                .collect()
+        } else {
+            self.menus
+                .iter()
+                .flat_map(|actions| actions.iter())
+                .collect()
+        }
+    }
+
+    /// Whether pause and restart are held.
+    fn pressed(&self) -> (bool, bool) {
+        let live = self.live_actions();
+        let fired = |state: &ActionState| *state == ActionState::Fired;
+        (
+            self.pause.iter_many(live.iter().copied()).any(fired),
+            self.restart.iter_many(live.iter().copied()).any(fired),
+        )
+    }
+}
+
+/// The buttons of the top-most menu, in reading order.
+fn navigable_buttons(
+    buttons: &Query<(Entity, &UiGlobalTransform), With<MenuButton>>,
+    parents: &Query<&ChildOf>,
+    layers: &Query<&GlobalZIndex>,
+) -> Vec<Entity> {
+    let layer = |entity: Entity| {
+        parents
+            .iter_ancestors(entity)
+            .last()
+            .and_then(|root| layers.get(root).ok())
+            .map_or(0, |index| index.0)
+    };
+    let top = buttons
+        .iter()
+        .map(|(entity, _)| layer(entity))
+        .max()
+        .unwrap_or_default();
+    let mut navigable: Vec<_> = buttons
+        .iter()
+        .filter(|&(entity, _)| layer(entity) == top)
+        .map(|(entity, transform)| (entity, transform.translation))
+        .collect();
+    navigable.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    navigable.into_iter().map(|(entity, _)| entity).collect()
+}
+
+pub(super) fn read_menu_input(
+    mut commands: Commands,
+    mut held: Local<HeldActions>,
+    navigate: Query<&ActionValue, With<Action<Navigate>>>,
+    confirm: Query<&ActionState, With<Action<Confirm>>>,
+    back: Query<&ActionState, With<Action<Back>>>,
+    pause_actions: PauseActions,
+    listening: Res<Listening>,
+    pause_state: Option<Res<State<PauseState>>>,
+    mut next_pause: Option<ResMut<NextState<PauseState>>>,
+    controls: Query<(), With<ControlsScreen>>,
+    buttons: Query<(Entity, &UiGlobalTransform), With<MenuButton>>,
+    actions: Query<&MenuButton>,
+    focused: Query<Entity, With<MenuFocus>>,
+    parents: Query<&ChildOf>,
+    layers: Query<&GlobalZIndex>,
+) {
+    let step = navigate
+        .iter()
+        .map(|value| value.as_axis2d())
+        .find(|value| value.length() > NAVIGATE_THRESHOLD);
+    let edge = |held: &mut bool, now: bool| {
+        let just_pressed = now && !*held;
+        *held = now;
+        just_pressed
+    };
+    let navigate_pressed = edge(&mut held.navigate, step.is_some());
+    let confirm_pressed = edge(&mut held.confirm, pressed(&confirm));
+    let back_pressed = edge(&mut held.back, pressed(&back));
+    let (pause, restart) = pause_actions.pressed();
+    let pause_pressed = edge(&mut held.pause, pause);
+    let restart_pressed = edge(&mut held.restart, restart);
+
+    // The controls screen is taking the next input as a binding.
+    if listening.0.is_some() {
+        return;
+    }
+
+    if restart_pressed {
+        commands.trigger(MenuAction::Retry);
+        return;
+    }
+    let controls_open = !controls.is_empty();
+    if (back_pressed || pause_pressed) && controls_open {
+        commands.trigger(MenuAction::Back);
+        return;
+    }
+    if pause_pressed {
+        if let (Some(state), Some(next)) = (pause_state, next_pause.as_mut()) {
+            next.set(match state.get() {
+                PauseState::Running => PauseState::Paused,
+                PauseState::Paused => PauseState::Running,
+            });
+        }
+        return;
+    }
+    if back_pressed {
+        commands.trigger(MenuAction::Resume);
+        return;
+    }
+
+    let current = focused.iter().next();
+    if confirm_pressed {
+        if let Some(button) = current.and_then(|entity| actions.get(entity).ok()) {
+            commands.trigger(button.0);
+        }
+        return;
+    }
+    let Some(step) = step.filter(|_| navigate_pressed) else {
+        return;
+    };
+
+    let navigable = navigable_buttons(&buttons, &parents, &layers);
+    if navigable.is_empty() {
+        return;
+    }
+    // Down and right move forwards through the buttons, up and left back.
+    let forwards = if step.y.abs() > step.x.abs() {
+        step.y < 0.0
+    } else {
+        step.x > 0.0
+    };
+    let index = current
+        .and_then(|entity| navigable.iter().position(|&other| other == entity))
+        .map_or(0, |index| {
+            if forwards {
+                (index + 1) % navigable.len()
+            } else {
+                (index + navigable.len() - 1) % navigable.len()
+            }
+        });
+    if let Some(current) = current {
+        commands.entity(current).remove::<MenuFocus>();
+    }
+    commands.entity(navigable[index]).insert(MenuFocus);
+}
+
+/// Keeps the focus on the top-most menu, so a gamepad can press something straight away.
+fn focus_first_button(
+    mut commands: Commands,
+    buttons: Query<(Entity, &UiGlobalTransform), With<MenuButton>>,
+    focused: Query<Entity, With<MenuFocus>>,
+    parents: Query<&ChildOf>,
+    layers: Query<&GlobalZIndex>,
+) {
+    let navigable = navigable_buttons(&buttons, &parents, &layers);
+    if focused.iter().any(|entity| navigable.contains(&entity)) {
+        return;
+    }
+    for entity in focused.iter() {
+        commands.entity(entity).remove::<MenuFocus>();
+    }
+    if let Some(&first) = navigable.first() {
+        commands.entity(first).insert(MenuFocus);
+    }
+}
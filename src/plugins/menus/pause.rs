@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{
+    plugins::{fonts::FontAssets, input::control_prompts},
+    states::PauseState,
+};
+
+use super::{MenuAction, menu_button, menu_root, menu_text};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(resume)
+        .add_systems(
+            OnEnter(PauseState::Paused),
+            (spawn_pause_screen, pause_time),
+        )
+        .add_systems(OnExit(PauseState::Paused), unpause_time);
+}
+
+fn spawn_pause_screen(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands.spawn((
+        Name::new("PauseScreen"),
+        menu_root(),
+        DespawnOnExit(PauseState::Paused),
+        children![
+            menu_text(&fonts, "PAUSED", 72.0),
+            control_prompts(),
+            menu_button(&fonts, "Resume", MenuAction::Resume),
+            menu_button(&fonts, "Restart", MenuAction::Retry),
+            menu_button(&fonts, "Controls", MenuAction::Controls),
+            menu_button(&fonts, "Quit", MenuAction::Quit),
+        ],
+    ));
+}
+
+/// Stops virtual time, which halts physics and every timer driven by it.
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// Leaves the pause menu when resuming, and when restarting, so the new run starts unpaused.
+fn resume(action: On<MenuAction>, next_pause: Option<ResMut<NextState<PauseState>>>) {
+    if !matches!(*action, MenuAction::Resume | MenuAction::Retry) {
+        return;
+    }
+    if let Some(mut next_pause) = next_pause {
+        next_pause.set(PauseState::Running);
+    }
+}
@@ -0,0 +1,70 @@
+//! Reconnect overlay, shown over the pause menu while a player's gamepad is disconnected. It
+//! goes away once every waiting player has a pad again; resuming carries on without it.
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{AwaitingGamepad, PlayerNumber},
+    plugins::fonts::FontAssets,
+    states::PauseState,
+};
+
+use super::{MenuAction, menu_button, menu_root, menu_text};
+
+#[derive(Component)]
+struct ReconnectOverlay;
+
+#[derive(Component)]
+struct ReconnectMessage;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_reconnect_overlay.run_if(in_state(PauseState::Paused)),
+    );
+}
+
+fn reconnect_message(waiting: &[PlayerNumber]) -> String {
+    let labels: Vec<_> = waiting.iter().map(|number| number.label()).collect();
+    format!("Connect a controller for {}", labels.join(", "))
+}
+
+fn update_reconnect_overlay(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    waiting: Query<&PlayerNumber, With<AwaitingGamepad>>,
+    overlays: Query<Entity, With<ReconnectOverlay>>,
+    mut messages: Query<&mut Text, With<ReconnectMessage>>,
+) {
+    let mut waiting: Vec<_> = waiting.iter().copied().collect();
+    waiting.sort();
+    if waiting.is_empty() {
+        for overlay in overlays.iter() {
+            commands.entity(overlay).despawn();
+        }
+        return;
+    }
+
+    let message = reconnect_message(&waiting);
+    if overlays.is_empty() {
+        commands.spawn((
+            Name::new("ReconnectOverlay"),
+            ReconnectOverlay,
+            menu_root(),
+            // Above the pause menu, below the controls screen.
+            GlobalZIndex(15),
+            DespawnOnExit(PauseState::Paused),
+            children![
+                menu_text(&fonts, "RECONNECT CONTROLLER", 64.0),
+                (menu_text(&fonts, message, 36.0), ReconnectMessage),
+                menu_button(&fonts, "Resume", MenuAction::Resume),
+            ],
+        ));
+        return;
+    }
+    for mut text in messages.iter_mut() {
+        if text.0 != message {
+            text.0 = message.clone();
+        }
+    }
+}
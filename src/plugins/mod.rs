@@ -4,4 +4,5 @@ pub(super) mod defaults;
 pub(super) mod fonts;
 pub(super) mod game;
 pub(super) mod input;
+pub(super) mod menus;
 pub(super) mod physics;
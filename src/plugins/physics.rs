@@ -1,6 +1,17 @@
 use avian2d::{math::Vector, prelude::*};
 use bevy::prelude::*;
 
+/// Collision layers. Bodies without [`CollisionLayers`] sit on [`GameLayer::Default`].
+#[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
+pub enum GameLayer {
+    #[default]
+    Default,
+    Player,
+    Enemy,
+    PlayerProjectile,
+    EnemyProjectile,
+}
+
 pub(crate) fn plugin(app: &mut App) {
     app.insert_resource(Gravity(Vector::NEG_Y * 1200.0));
 }
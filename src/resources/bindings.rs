@@ -0,0 +1,265 @@
+use std::fmt;
+
+use bevy::{
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant},
+};
+
+use crate::events::BindingConflict;
+
+/// A player control that can be rebound. The left stick always drives movement as well, since
+/// an axis can't be captured by pressing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Control {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Shoot,
+    Interact,
+}
+
+impl Control {
+    pub const ALL: [Self; 5] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Jump,
+        Self::Shoot,
+        Self::Interact,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MoveLeft => "Move Left",
+            Self::MoveRight => "Move Right",
+            Self::Jump => "Jump",
+            Self::Shoot => "Shoot",
+            Self::Interact => "Interact",
+        }
+    }
+
+    /// Name used in bindings files.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::MoveLeft => "move_left",
+            Self::MoveRight => "move_right",
+            Self::Jump => "jump",
+            Self::Shoot => "shoot",
+            Self::Interact => "interact",
+        }
+    }
+}
+
+/// A key or gamepad button a control can be bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    /// Whether both bindings come from the same kind of device.
+    pub fn same_device(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Key(_), Self::Key(_)) | (Self::Gamepad(_), Self::Gamepad(_))
+        )
+    }
+
+    /// `key:Name` or `gamepad:Name`, using the Bevy variant names.
+    pub fn to_text(self) -> String {
+        match self {
+            Self::Key(key) => format!("key:{key:?}"),
+            Self::Gamepad(button) => format!("gamepad:{button:?}"),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Option<Self> {
+        let (device, name) = text.trim().split_once(':')?;
+        match device {
+            "key" => unit_variant(name).map(Self::Key),
+            "gamepad" => unit_variant(name).map(Self::Gamepad),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key:?}"),
+            Self::Gamepad(button) => write!(f, "Pad {button:?}"),
+        }
+    }
+}
+
+/// The part of a keyboard one player uses when two share it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyboardHalf {
+    Whole,
+    Left,
+    Right,
+}
+
+impl KeyboardHalf {
+    /// Whether `key` belongs to this half. Space and the keys left of `Y`, `H` and `B` count as
+    /// the left; everything else, arrows included, as the right.
+    pub fn contains(self, key: KeyCode) -> bool {
+        use KeyCode::*;
+        let left = matches!(
+            key,
+            Escape
+                | Backquote
+                | Digit1
+                | Digit2
+                | Digit3
+                | Digit4
+                | Digit5
+                | Tab
+                | KeyQ
+                | KeyW
+                | KeyE
+                | KeyR
+                | KeyT
+                | CapsLock
+                | KeyA
+                | KeyS
+                | KeyD
+                | KeyF
+                | KeyG
+                | ShiftLeft
+                | KeyZ
+                | KeyX
+                | KeyC
+                | KeyV
+                | ControlLeft
+                | SuperLeft
+                | AltLeft
+                | Space
+        );
+        match self {
+            Self::Whole => true,
+            Self::Left => left,
+            Self::Right => !left,
+        }
+    }
+}
+
+/// Builds a fieldless enum variant from its name.
+fn unit_variant<T: FromReflect>(name: &str) -> Option<T> {
+    T::from_reflect(&DynamicEnum::new(name.trim(), DynamicVariant::Unit))
+}
+
+/// The inputs bound to each control. Loaded as an asset from `assets/config/*.bindings` and
+/// kept as the live resource the player's input context is built from.
+#[derive(Asset, Resource, TypePath, Clone, Debug, Default, PartialEq)]
+pub struct ControlBindings {
+    bindings: Vec<(Control, Vec<InputBinding>)>,
+}
+
+impl ControlBindings {
+    pub fn get(&self, control: Control) -> &[InputBinding] {
+        self.bindings
+            .iter()
+            .find(|(existing, _)| *existing == control)
+            .map(|(_, bindings)| bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, control: Control, bindings: Vec<InputBinding>) {
+        match self
+            .bindings
+            .iter_mut()
+            .find(|(existing, _)| *existing == control)
+        {
+            Some((_, existing)) => *existing = bindings,
+            None => self.bindings.push((control, bindings)),
+        }
+    }
+
+    /// The control other than `except` that `binding` is bound to.
+    pub fn bound_to(&self, binding: InputBinding, except: Control) -> Option<Control> {
+        Control::ALL
+            .into_iter()
+            .find(|&control| control != except && self.get(control).contains(&binding))
+    }
+
+    /// Binds `binding` to `control` in place of its other bindings from the same device.
+    /// Refuses inputs already used by another control.
+    pub fn rebind(
+        &mut self,
+        control: Control,
+        binding: InputBinding,
+    ) -> Result<(), BindingConflict> {
+        if let Some(existing) = self.bound_to(binding, control) {
+            return Err(BindingConflict {
+                binding,
+                existing,
+                requested: control,
+            });
+        }
+        let mut bindings: Vec<_> = self
+            .get(control)
+            .iter()
+            .copied()
+            .filter(|other| !other.same_device(binding))
+            .collect();
+        bindings.push(binding);
+        self.set(control, bindings);
+        Ok(())
+    }
+
+    /// Every input bound to more than one control.
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let mut conflicts = Vec::new();
+        for (index, (existing, bindings)) in self.bindings.iter().enumerate() {
+            for (requested, others) in &self.bindings[index + 1..] {
+                for binding in bindings.iter().filter(|binding| others.contains(binding)) {
+                    conflicts.push(BindingConflict {
+                        binding: *binding,
+                        existing: *existing,
+                        requested: *requested,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// One `control = input, input` line per control.
+    pub fn to_text(&self) -> String {
+        self.bindings
+            .iter()
+            .map(|(control, bindings)| {
+                let bindings: Vec<_> = bindings.iter().map(|binding| binding.to_text()).collect();
+                format!("{} = {}", control.id(), bindings.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Parses [`to_text`](Self::to_text) output. Blank lines and `#` comments are ignored;
+    /// unknown controls and inputs are skipped.
+    pub fn from_text(text: &str) -> Self {
+        let mut result = Self::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((id, bindings)) = line.split_once('=') else {
+                continue;
+            };
+            let Some(control) = Control::ALL
+                .into_iter()
+                .find(|control| control.id() == id.trim())
+            else {
+                continue;
+            };
+            let bindings = bindings
+                .split(',')
+                .filter_map(InputBinding::from_text)
+                .collect();
+            result.set(control, bindings);
+        }
+        result
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::components::CollectibleKind;
+
+/// How a collectible kind looks, sounds and shows up in the HUD.
+#[derive(Clone, Debug)]
+pub struct CollectibleKindInfo {
+    pub label: &'static str,
+    /// Asset path of the sprite; untextured kinds are drawn as `color` squares.
+    pub texture: Option<&'static str>,
+    pub color: Color,
+    pub size: Vec2,
+    pub radius: f32,
+    /// Asset path of the pickup sound.
+    pub sound: Option<&'static str>,
+    /// Whether the HUD shows a running count for this kind.
+    pub hud_counter: bool,
+    /// Score for each unit of value picked up, before the combo multiplier.
+    pub points: u32,
+}
+
+/// Registry of collectible kinds, in registration order.
+#[derive(Resource, Clone, Default)]
+pub struct CollectibleKinds {
+    kinds: Vec<(CollectibleKind, CollectibleKindInfo)>,
+}
+
+impl CollectibleKinds {
+    pub fn register(&mut self, kind: CollectibleKind, info: CollectibleKindInfo) {
+        match self
+            .kinds
+            .iter_mut()
+            .find(|(existing, _)| *existing == kind)
+        {
+            Some((_, existing)) => *existing = info,
+            None => self.kinds.push((kind, info)),
+        }
+    }
+
+    pub fn get(&self, kind: CollectibleKind) -> Option<&CollectibleKindInfo> {
+        self.kinds
+            .iter()
+            .find(|(existing, _)| *existing == kind)
+            .map(|(_, info)| info)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (CollectibleKind, &CollectibleKindInfo)> {
+        self.kinds.iter().map(|(kind, info)| (*kind, info))
+    }
+}
+
+/// Running totals per kind for the current run.
+#[derive(Resource, Default, Debug)]
+pub struct CollectedCounts(pub HashMap<CollectibleKind, u32>);
+
+impl CollectedCounts {
+    pub fn get(&self, kind: CollectibleKind) -> u32 {
+        self.0.get(&kind).copied().unwrap_or_default()
+    }
+}
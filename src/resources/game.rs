@@ -1,8 +1,103 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, platform::collections::HashMap, prelude::*};
 
+use crate::{
+    components::{InputDevices, LogicId, PlayerNumber},
+    resources::KeyboardHalf,
+};
+
+/// Coins in the current level. `total` counts the coins actually spawned, so the HUD always
+/// matches the level's content. `arrived` trails `collected` while coins fly to the HUD.
 #[derive(Resource, Default)]
 pub struct CoinState {
     pub collected: usize,
+    pub arrived: usize,
+    pub total: usize,
+    /// Coins each player has collected, by [`PlayerNumber`](crate::components::PlayerNumber).
+    pub by_player: Vec<usize>,
+}
+
+impl CoinState {
+    pub fn collected_by(&self, player: usize) -> usize {
+        self.by_player.get(player).copied().unwrap_or_default()
+    }
+
+    pub fn add(&mut self, player: Option<usize>, value: usize) {
+        self.collected += value;
+        if let Some(player) = player {
+            if self.by_player.len() <= player {
+                self.by_player.resize(player + 1, 0);
+            }
+            self.by_player[player] += value;
+        }
+    }
+}
+
+/// The switches currently holding each [`LogicId`] on. A target stays active while any of its
+/// switches is, so several switches can share one ID without overriding each other.
+#[derive(Resource, Default, Debug)]
+pub struct LogicInputs(pub HashMap<LogicId, EntityHashSet>);
+
+impl LogicInputs {
+    /// Records whether `source` is holding `target` on, and returns whether `target` is now active.
+    pub fn set(&mut self, target: LogicId, source: Entity, active: bool) -> bool {
+        let sources = self.0.entry(target).or_default();
+        if active {
+            sources.insert(source);
+        } else {
+            sources.remove(&source);
+        }
+        !sources.is_empty()
+    }
+}
+
+/// Attempts left in the current run. Reaching zero ends the run.
+#[derive(Resource, Debug)]
+pub struct Lives {
+    pub remaining: u32,
+    pub max: u32,
+}
+
+impl Lives {
+    pub const fn new(max: u32) -> Self {
+        Self {
+            remaining: max,
+            max,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.remaining = self.max;
+    }
+}
+
+/// Everyone who has joined the run and the devices they play on. Levels are respawned from
+/// this, so joined players come back after a retry or on the next level.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct JoinedPlayers(pub Vec<(PlayerNumber, InputDevices)>);
+
+impl Default for JoinedPlayers {
+    /// The first player on the whole keyboard.
+    fn default() -> Self {
+        Self(vec![(
+            PlayerNumber(0),
+            InputDevices {
+                keyboard: Some(KeyboardHalf::Whole),
+                gamepad: None,
+            },
+        )])
+    }
+}
+
+/// Seed for anything random in a run. Replays restore it, so whatever draws on it plays out
+/// the same way again.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunSeed(pub u64);
+
+/// Stats shown on the game-over screen.
+#[derive(Resource, Default, Debug)]
+pub struct RunStats {
+    pub elapsed_secs: f32,
+    pub deaths: u32,
 }
 
 #[derive(Resource)]
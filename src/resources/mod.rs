@@ -1,3 +1,13 @@
+pub(super) mod bindings;
+pub(super) mod collectibles;
 pub(super) mod game;
+pub(super) mod replay;
+pub(super) mod score;
+pub(super) mod settings;
 
+pub use bindings::*;
+pub use collectibles::*;
 pub use game::*;
+pub use replay::*;
+pub use score::*;
+pub use settings::*;
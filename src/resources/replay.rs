@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+/// One player's actions during one fixed tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlayerActions {
+    pub movement: Vec2,
+    pub jump: bool,
+    pub shoot: bool,
+    pub interact: bool,
+}
+
+impl PlayerActions {
+    /// `x,y` followed by `J`, `S` and `I` for each action held.
+    fn to_text(self) -> String {
+        let mut text = format!("{},{}", self.movement.x, self.movement.y);
+        for (held, flag) in [(self.jump, 'J'), (self.shoot, 'S'), (self.interact, 'I')] {
+            if held {
+                text.push(flag);
+            }
+        }
+        text
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        let flags = text.trim_start_matches(|c: char| c != 'J' && c != 'S' && c != 'I');
+        let (x, y) = text[..text.len() - flags.len()].split_once(',')?;
+        if flags.chars().any(|flag| !"JSI".contains(flag)) {
+            return None;
+        }
+        Some(Self {
+            movement: Vec2::new(x.parse().ok()?, y.parse().ok()?),
+            jump: flags.contains('J'),
+            shoot: flags.contains('S'),
+            interact: flags.contains('I'),
+        })
+    }
+}
+
+/// Every player's actions for each fixed tick of a run, along with what's needed to start the
+/// run the same way again.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    /// Version of the build that recorded it.
+    pub version: String,
+    pub level: String,
+    /// The run's [`RunSeed`](super::RunSeed).
+    pub seed: u64,
+    /// Per tick, each player's actions by player number. `None` for players who hadn't joined.
+    pub ticks: Vec<Vec<Option<PlayerActions>>>,
+}
+
+impl Replay {
+    pub fn new(level: impl Into<String>, seed: u64) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").into(),
+            level: level.into(),
+            seed,
+            ticks: Vec::new(),
+        }
+    }
+
+    /// A header, then one line per tick with the players separated by `|` and `-` for a
+    /// player who hasn't joined. A run of identical ticks is written once, followed by `*n`
+    /// for the `n` repeats.
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("version {}", self.version),
+            format!("level {}", self.level),
+            format!("seed {}", self.seed),
+        ];
+        let mut repeats = 0;
+        for (index, tick) in self.ticks.iter().enumerate() {
+            if index > 0 && self.ticks[index - 1] == *tick {
+                repeats += 1;
+                continue;
+            }
+            if repeats > 0 {
+                lines.push(format!("*{repeats}"));
+                repeats = 0;
+            }
+            let players: Vec<_> = tick
+                .iter()
+                .map(|player| player.map_or("-".into(), PlayerActions::to_text))
+                .collect();
+            lines.push(players.join("|"));
+        }
+        if repeats > 0 {
+            lines.push(format!("*{repeats}"));
+        }
+        lines.join("\n")
+    }
+
+    /// Parses [`to_text`](Self::to_text) output. Unlike settings files, a replay with any
+    /// malformed line is rejected whole, since skipping a tick would throw the run off.
+    pub fn from_text(text: &str) -> Option<Self> {
+        // Blank lines are kept: they are ticks without any players.
+        let mut lines = text.lines().map(str::trim);
+        let mut header = |key: &str| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(key))
+                .map(|value| value.trim().to_owned())
+        };
+        let mut replay = Self {
+            version: header("version")?,
+            level: header("level")?,
+            seed: header("seed")?.parse().ok()?,
+            ticks: Vec::new(),
+        };
+        for line in lines {
+            if let Some(repeats) = line.strip_prefix('*') {
+                let tick = replay.ticks.last()?.clone();
+                let repeats: usize = repeats.parse().ok()?;
+                replay.ticks.extend(std::iter::repeat_n(tick, repeats));
+                continue;
+            }
+            let tick = line
+                .split('|')
+                .filter(|player| !player.is_empty())
+                .map(|player| match player {
+                    "-" => Some(None),
+                    player => PlayerActions::from_text(player).map(Some),
+                })
+                .collect::<Option<_>>()?;
+            replay.ticks.push(tick);
+        }
+        Some(replay)
+    }
+}
+
+/// Whether runs are recorded or replayed. Recording is the default, so the last run can always
+/// be saved as a repro; a replay stays in effect for every run until this is replaced.
+#[derive(Resource, Clone, Debug)]
+pub enum InputReplay {
+    /// Live input, recorded into the replay.
+    Recording(Replay),
+    /// The replay's input in place of live input, from `tick` on. Live input takes over once
+    /// the ticks run out.
+    Replaying { replay: Replay, tick: usize },
+}
+
+impl Default for InputReplay {
+    fn default() -> Self {
+        Self::Recording(Replay::default())
+    }
+}
+
+impl InputReplay {
+    pub fn play(replay: Replay) -> Self {
+        Self::Replaying { replay, tick: 0 }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self, Self::Replaying { .. })
+    }
+
+    pub fn replay(&self) -> &Replay {
+        match self {
+            Self::Recording(replay) | Self::Replaying { replay, .. } => replay,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Points scored in the current level.
+#[derive(Resource, Default, Debug)]
+pub struct Score {
+    pub points: u32,
+    /// Pickups chained so far. Drops back to zero when `combo_timer` runs out.
+    pub combo: u32,
+    pub combo_timer: Timer,
+    pub level_secs: f32,
+    /// Whether the player has taken a hit this level, which forfeits the no-damage bonus.
+    pub damaged: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreBonus {
+    pub label: &'static str,
+    pub points: u32,
+}
+
+/// The breakdown shown on the level-complete screen.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LevelResult {
+    pub level: String,
+    pub pickups: u32,
+    pub bonuses: Vec<ScoreBonus>,
+    pub total: u32,
+    pub secs: f32,
+    /// Position in the level's high-score table, if the run made it in.
+    pub rank: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HighScoreEntry {
+    pub points: u32,
+    pub secs: f32,
+}
+
+/// Best scores per level ID, highest first.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct HighScores {
+    pub levels: HashMap<String, Vec<HighScoreEntry>>,
+}
+
+impl HighScores {
+    pub fn get(&self, level: &str) -> &[HighScoreEntry] {
+        self.levels
+            .get(level)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Inserts the entry, keeping at most `limit` per level. Returns its rank if it was kept.
+    pub fn insert(&mut self, level: &str, entry: HighScoreEntry, limit: usize) -> Option<usize> {
+        let table = self.levels.entry(level.to_owned()).or_default();
+        // Ties go to the faster run.
+        let rank = table
+            .iter()
+            .position(|other| {
+                entry.points > other.points
+                    || (entry.points == other.points && entry.secs < other.secs)
+            })
+            .unwrap_or(table.len());
+        if rank >= limit {
+            return None;
+        }
+        table.insert(rank, entry);
+        table.truncate(limit);
+        Some(rank)
+    }
+
+    /// One `level<TAB>points<TAB>secs` line per entry.
+    pub fn to_text(&self) -> String {
+        let mut lines = Vec::new();
+        for (level, table) in &self.levels {
+            for entry in table {
+                lines.push(format!("{level}\t{}\t{}", entry.points, entry.secs));
+            }
+        }
+        lines.sort();
+        lines.join("\n")
+    }
+
+    /// Parses [`to_text`](Self::to_text) output, skipping malformed lines.
+    pub fn from_text(text: &str) -> Self {
+        let mut scores = Self::default();
+        for line in text.lines() {
+            let mut fields = line.split('\t');
+            let (Some(level), Some(points), Some(secs)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let (Ok(points), Ok(secs)) = (points.parse(), secs.parse()) else {
+                continue;
+            };
+            scores.insert(level, HighScoreEntry { points, secs }, usize::MAX);
+        }
+        scores
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Accessibility options.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Accessibility {
+    /// Turns off screen shake, camera kicks and hit-stop.
+    pub reduced_motion: bool,
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GameState {
+    /// The level is spawned and running. Level entities despawn when leaving this state.
+    #[default]
+    Playing,
+    GameOver,
+    LevelComplete,
+}
+
+/// Whether the running level is paused. Only exists while [`GameState::Playing`].
+#[derive(SubStates, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Which input context drives the game, following the state: gameplay input while a level is
+/// running, menu input everywhere else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    Gameplay,
+    Menu,
+}
+
+impl ComputedStates for InputMode {
+    type SourceStates = (GameState, Option<PauseState>);
+
+    fn compute((game, pause): (GameState, Option<PauseState>)) -> Option<Self> {
+        Some(match (game, pause) {
+            (GameState::Playing, Some(PauseState::Running)) => Self::Gameplay,
+            _ => Self::Menu,
+        })
+    }
+}
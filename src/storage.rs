@@ -0,0 +1,65 @@
+//! Small key-value persistence: a file per key on native, `localStorage` on web.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{fs, path::PathBuf};
+
+    const APP_DIR: &str = "starter";
+
+    fn data_dir() -> PathBuf {
+        let base = std::env::var_os("XDG_DATA_HOME")
+            .or_else(|| std::env::var_os("APPDATA"))
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(".local").join("share"))
+            })
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join(APP_DIR)
+    }
+
+    pub fn load(key: &str) -> Option<String> {
+        fs::read_to_string(data_dir().join(format!("{key}.txt"))).ok()
+    }
+
+    pub fn save(key: &str, value: &str) -> std::io::Result<()> {
+        let dir = data_dir();
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(format!("{key}.txt")), value)
+    }
+
+    pub fn remove(key: &str) -> std::io::Result<()> {
+        match fs::remove_file(data_dir().join(format!("{key}.txt"))) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub fn load(key: &str) -> Option<String> {
+        storage()?.get_item(key).ok().flatten()
+    }
+
+    pub fn save(key: &str, value: &str) -> std::io::Result<()> {
+        storage()
+            .and_then(|storage| storage.set_item(key, value).ok())
+            .ok_or_else(|| std::io::Error::other("localStorage is unavailable"))
+    }
+
+    pub fn remove(key: &str) -> std::io::Result<()> {
+        storage()
+            .and_then(|storage| storage.remove_item(key).ok())
+            .ok_or_else(|| std::io::Error::other("localStorage is unavailable"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::{load, remove, save};
+#[cfg(target_arch = "wasm32")]
+pub use web::{load, remove, save};
@@ -1,9 +1,112 @@
 use bevy::prelude::Vec2;
 use bevy_enhanced_input::prelude::ActionState;
 
-pub fn format_hud_text(fps: f64, movement: Vec2, jump_state: ActionState) -> String {
+use crate::{
+    components::{ActivePowerUps, Health, PlayerNumber},
+    resources::{CoinState, Lives},
+};
+
+/// The frame rate followed by one block per player.
+pub fn format_hud_text(fps: f64, players: &[String]) -> String {
+    let mut text = format!("FPS: {fps:>4.0}");
+    for player in players {
+        text.push('\n');
+        text.push_str(player);
+    }
+    text
+}
+
+pub fn format_player_hud(
+    number: PlayerNumber,
+    movement: Vec2,
+    jump_state: ActionState,
+    health: &Health,
+    coins: usize,
+) -> String {
     format!(
-        "FPS: {:>4.0}\nMove: [{:>4.2}, {:>4.2}] (WASD/Arrows/Stick)\nJump: {:?} (Space/W/Up/South)",
-        fps, movement.x, movement.y, jump_state
+        "{0} Move: [{1:>4.2}, {2:>4.2}]\n{0} Jump: {3:?}\n{0} Health: {4}/{5} Coins: {6}",
+        number.label(),
+        movement.x,
+        movement.y,
+        jump_state,
+        health.current,
+        health.max,
+        coins
     )
 }
+
+/// Formats seconds as `m:ss.s`.
+pub fn format_run_time(seconds: f32) -> String {
+    // Round before splitting, so 59.96 carries into the minutes instead of showing as 0:60.0.
+    let tenths = (seconds * 10.0).round() as u32;
+    let (minutes, tenths) = (tenths / 600, tenths % 600);
+    format!("{minutes}:{:02}.{}", tenths / 10, tenths % 10)
+}
+
+pub fn format_lives(lives: &Lives) -> String {
+    format!("Lives: {}", lives.remaining)
+}
+
+/// The run's coins, broken down by player when more than one collected any.
+pub fn format_coins(coin_state: &CoinState) -> String {
+    if coin_state.by_player.len() < 2 {
+        return format!("Coins: {}", coin_state.collected);
+    }
+    let players: Vec<_> = coin_state
+        .by_player
+        .iter()
+        .enumerate()
+        .map(|(number, coins)| format!("{} {coins}", PlayerNumber(number).label()))
+        .collect();
+    format!("Coins: {} ({})", coin_state.collected, players.join(", "))
+}
+
+pub fn format_coin_count(collected: usize, total: usize) -> String {
+    format!("{collected} / {total}")
+}
+
+/// One line per active power-up, e.g. `Speed x2 3.4s`.
+pub fn format_power_ups(active: &ActivePowerUps) -> String {
+    active
+        .0
+        .iter()
+        .map(|power_up| {
+            let stacks = if power_up.stacks > 1 {
+                format!(" x{}", power_up.stacks)
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{} {:.1}s",
+                power_up.kind.label(),
+                stacks,
+                power_up.timer.remaining_secs()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Score with the live combo multiplier, omitted when there is no chain.
+pub fn format_score(points: u32, multiplier: u32) -> String {
+    if multiplier > 1 {
+        format!("Score: {points} x{multiplier}")
+    } else {
+        format!("Score: {points}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_times_round_into_the_next_minute() {
+        assert_eq!(format_run_time(0.0), "0:00.0");
+        assert_eq!(format_run_time(59.94), "0:59.9");
+        for seconds in [59.95, 59.96, 59.99] {
+            assert_eq!(format_run_time(seconds), "1:00.0");
+        }
+        assert_eq!(format_run_time(125.25), "2:05.3");
+    }
+}
@@ -1,8 +1,10 @@
 use avian2d::prelude::*;
-use bevy::prelude::*;
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
 use starter::{
-    COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, Coin, CoinSlot, CoinState, Player,
-    collect_coins, update_coin_counter,
+    COIN_SLOT_EMPTY_COLOR, COIN_SLOT_FILLED_COLOR, CoinSlot, CoinState, Collectible,
+    CollectibleKind, CollectibleKindInfo, CollectibleKinds, Ground, LevelData, Platform, Player,
+    RegisterCollectibleExt, Surroundings, add_coins, announce_coin_collected, collect_collectibles,
+    count_spawned_coins, detect_surroundings, spawn_collectibles, update_coin_counter,
 };
 
 fn setup_app() -> App {
@@ -12,19 +14,27 @@ fn setup_app() -> App {
 }
 
 #[test]
-fn collect_coins_despawns_and_counts() {
+fn collect_collectibles_despawns_and_counts_coins() {
     let mut app = setup_app();
     // Prepare minimal state so collection logic has data to mutate.
     app.insert_resource(CoinState::default());
 
     // Arrange a colliding player to exercise the collection path.
-    let coin_entity = app.world_mut().spawn(Coin).id();
+    let coin_entity = app
+        .world_mut()
+        .spawn((
+            Collectible::new(CollectibleKind::COIN, 1),
+            Transform::default(),
+        ))
+        .id();
     let mut colliding = CollidingEntities::default();
-    colliding.insert(coin_entity); // Simulate collision so collect_coins runs.
+    colliding.insert(coin_entity); // Simulate collision so collect_collectibles runs.
     app.world_mut().spawn((Player, colliding));
 
-    // Drive the schedule once to apply the system effects.
-    app.add_systems(Update, collect_coins);
+    // Drive the schedule once to apply the system effects; the coin effect is an observer.
+    app.add_systems(Update, collect_collectibles);
+    app.add_observer(announce_coin_collected);
+    app.add_observer(add_coins);
     app.update();
 
     // Validate that collection removed the coin and updated the counter.
@@ -32,12 +42,86 @@ fn collect_coins_despawns_and_counts() {
     assert_eq!(app.world().resource::<CoinState>().collected, 1);
 }
 
+#[test]
+fn coin_touched_by_two_players_counts_once() {
+    let mut app = setup_app();
+    app.insert_resource(CoinState::default());
+
+    let coin_entity = app
+        .world_mut()
+        .spawn((
+            Collectible::new(CollectibleKind::COIN, 1),
+            Transform::default(),
+        ))
+        .id();
+    // Both players overlap the coin in the same frame.
+    for _ in 0..2 {
+        let mut colliding = CollidingEntities::default();
+        colliding.insert(coin_entity);
+        app.world_mut().spawn((Player, colliding));
+    }
+
+    app.add_systems(Update, collect_collectibles);
+    app.add_observer(announce_coin_collected);
+    app.add_observer(add_coins);
+    app.update();
+
+    assert!(app.world().get_entity(coin_entity).is_err());
+    assert_eq!(app.world().resource::<CoinState>().collected, 1);
+}
+
+#[test]
+fn coin_total_matches_the_coins_the_level_spawns() {
+    let mut app = setup_app();
+    app.add_plugins(AssetPlugin::default()); // Spawning takes an asset server.
+    app.insert_resource(CoinState::default());
+    // Untextured kinds so nothing needs loading; other kinds in the level are skipped.
+    for kind in [CollectibleKind::COIN, CollectibleKind::GEM] {
+        app.register_collectible(
+            kind,
+            CollectibleKindInfo {
+                label: kind.0,
+                texture: None,
+                color: Color::WHITE,
+                size: Vec2::splat(16.0),
+                radius: 8.0,
+                sound: None,
+                hud_counter: true,
+                points: 1,
+            },
+        );
+    }
+    app.add_observer(count_spawned_coins);
+
+    let level = LevelData::default();
+    app.world_mut()
+        .run_system_once(
+            move |mut commands: Commands,
+                  assets: Res<AssetServer>,
+                  kinds: Res<CollectibleKinds>| {
+                spawn_collectibles(&mut commands, &assets, &kinds, &level.collectibles);
+            },
+        )
+        .unwrap();
+
+    // Compare against what is actually in the world, not the level data.
+    let spawned: u32 = app
+        .world_mut()
+        .query::<&Collectible>()
+        .iter(app.world())
+        .filter(|collectible| collectible.kind == CollectibleKind::COIN)
+        .map(|collectible| collectible.value)
+        .sum();
+    assert!(spawned > 0);
+    assert_eq!(app.world().resource::<CoinState>().total, spawned as usize);
+}
+
 #[test]
 fn update_coin_counter_sets_slot_colors() {
     let mut app = setup_app();
     // Use a known count to make the expected colors deterministic.
     app.insert_resource(CoinState::default());
-    app.world_mut().resource_mut::<CoinState>().collected = 2; // Trigger change detection with known count.
+    app.world_mut().resource_mut::<CoinState>().arrived = 2; // Trigger change detection with known count.
 
     // Seed UI slots so the system can update their colors.
     for index in 0..4 {
@@ -65,3 +149,52 @@ fn update_coin_counter_sets_slot_colors() {
         assert_eq!(image.color, expected);
     }
 }
+
+#[test]
+fn detect_surroundings_reports_ground_edges_and_walls() {
+    let mut app = setup_app();
+
+    // A floor under the mover and a wall to its right.
+    let floor = app
+        .world_mut()
+        .spawn((
+            Ground,
+            Platform {
+                size: Vec2::new(200.0, 20.0),
+            },
+            Transform::from_xyz(0.0, -30.0, 0.0),
+        ))
+        .id();
+    let wall = app
+        .world_mut()
+        .spawn((
+            Ground,
+            Platform {
+                size: Vec2::new(20.0, 200.0),
+            },
+            Transform::from_xyz(30.0, 0.0, 0.0),
+        ))
+        .id();
+    let mut colliding = CollidingEntities::default();
+    colliding.insert(floor);
+    colliding.insert(wall);
+    let mover = app
+        .world_mut()
+        .spawn((
+            Surroundings::new(Vec2::splat(40.0)),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+            colliding,
+        ))
+        .id();
+
+    app.add_systems(Update, detect_surroundings);
+    app.update();
+
+    let surroundings = app.world().get::<Surroundings>(mover).unwrap();
+    assert!(surroundings.is_grounded());
+    assert!(surroundings.wall_right);
+    assert!(!surroundings.wall_left);
+    // The floor spans -100..100, so the mover only reaches its edge near either end.
+    assert!(!surroundings.edge_ahead(0.0, 1.0));
+    assert!(surroundings.edge_ahead(85.0, 1.0));
+}